@@ -128,12 +128,18 @@ mod tests {
             shell: "/bin/bash".to_string(),
             base_path: PathBuf::from("/home/user/project"),
             retry_config: forge_domain::RetryConfig {
+                jitter: forge_domain::JitterMode::default(),
                 initial_backoff_ms: 1000,
                 min_delay_ms: 500,
                 backoff_factor: 2,
                 max_retry_attempts: 3,
                 retry_status_codes: vec![429, 500, 502, 503, 504],
                 max_delay: None,
+                max_elapsed: None,
+                additional_retryable_status_codes: vec![],
+                additional_non_retryable_status_codes: vec![],
+                per_status_backoff: std::collections::HashMap::new(),
+                retry_on_body_patterns: vec![],
             },
             max_search_lines: 25,
             fetch_truncation_limit: 55,
@@ -1,7 +1,8 @@
 use std::time::Duration;
 
-use backon::{ExponentialBuilder, Retryable};
-use forge_domain::{Error, RetryConfig};
+use backon::{BackoffBuilder, ExponentialBuilder, Retryable};
+use forge_domain::{Error, JitterMode, RetryConfig};
+use rand::Rng;
 
 pub async fn retry_with_config<F, Fut, T, C>(
     config: &RetryConfig,
@@ -13,13 +14,14 @@ where
     Fut: std::future::Future<Output = anyhow::Result<T>>,
     C: Fn(&anyhow::Error, Duration) + Send + Sync + 'static,
 {
-    let strategy = ExponentialBuilder::default()
-        .with_min_delay(Duration::from_millis(config.min_delay_ms))
-        .with_factor(config.backoff_factor as f32)
-        .with_max_times(config.max_retry_attempts)
-        .with_jitter();
+    let strategy = JitteredBackoff { inner: exponential_backoff(config), jitter: config.jitter };
+
+    let max_elapsed = config.max_elapsed.map(Duration::from_secs);
+    let start = tokio::time::Instant::now();
 
-    let retryable = operation.retry(&strategy).when(should_retry);
+    let retryable = operation
+        .retry(strategy)
+        .when(move |error| should_retry(error) && !elapsed_budget_exhausted(start, max_elapsed));
 
     match notify {
         Some(callback) => retryable.notify(callback).await,
@@ -27,6 +29,65 @@ where
     }
 }
 
+/// Builds the base (pre-jitter) exponential backoff schedule from `config`.
+/// Wiring `max_delay` through `with_max_delay` isn't just about respecting
+/// the configured ceiling - `backon` clamps each computed delay against it
+/// *before* multiplying by `backoff_factor` again, which is what keeps a
+/// misconfigured huge `backoff_factor`/`min_delay_ms` pair from repeatedly
+/// multiplying a `Duration` past its representable range and panicking.
+fn exponential_backoff(config: &RetryConfig) -> impl Iterator<Item = Duration> {
+    let mut builder = ExponentialBuilder::default()
+        .with_min_delay(Duration::from_millis(config.min_delay_ms))
+        .with_factor(config.backoff_factor as f32)
+        .with_max_times(config.max_retry_attempts);
+    if let Some(max_delay) = config.max_delay {
+        builder = builder.with_max_delay(Duration::from_secs(max_delay));
+    }
+    builder.build()
+}
+
+/// Whether the cumulative time spent on an operation (including backoff
+/// sleeps, since `start` is measured in wall-clock time) has reached
+/// `max_elapsed`. `None` means no elapsed-time budget, so the count cap
+/// (`max_retry_attempts`) is the only limit.
+fn elapsed_budget_exhausted(start: tokio::time::Instant, max_elapsed: Option<Duration>) -> bool {
+    max_elapsed.is_some_and(|budget| start.elapsed() >= budget)
+}
+
+/// Wraps a `backon` backoff iterator, applying `jitter` to every computed
+/// delay before it's handed back. Delegates the base exponential schedule to
+/// `backon` and only reshapes the individual delays, so `max_retry_attempts`
+/// and `min_delay_ms` keep meaning exactly what they did before jitter was
+/// configurable.
+struct JitteredBackoff<B> {
+    inner: B,
+    jitter: JitterMode,
+}
+
+impl<B: Iterator<Item = Duration>> Iterator for JitteredBackoff<B> {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Duration> {
+        self.inner
+            .next()
+            .map(|delay| apply_jitter(delay, self.jitter, &mut rand::thread_rng()))
+    }
+}
+
+/// Applies the AWS-style full/equal jitter formulas to a computed backoff
+/// delay. Takes the RNG as a parameter so tests can substitute a seeded one
+/// and assert deterministic bounds.
+fn apply_jitter(delay: Duration, mode: JitterMode, rng: &mut impl Rng) -> Duration {
+    match mode {
+        JitterMode::None => delay,
+        JitterMode::Equal => {
+            let half = delay / 2;
+            half + rng.gen_range(Duration::ZERO..=half.max(Duration::from_millis(1)))
+        }
+        JitterMode::Full => rng.gen_range(Duration::ZERO..=delay.max(Duration::from_millis(1))),
+    }
+}
+
 /// Determines if an error should trigger a retry attempt.
 ///
 /// This function checks if the error is a retryable domain error.
@@ -36,3 +97,126 @@ fn should_retry(error: &anyhow::Error) -> bool {
         .downcast_ref::<Error>()
         .is_some_and(|error| matches!(error, Error::Retryable(_)))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retry_with_config_trips_elapsed_budget_before_count_cap() {
+        // Fixture: a huge attempt count cap paired with a small elapsed-time
+        // budget, and a backoff long enough that a couple of sleeps exhaust it.
+        let config = RetryConfig::default()
+            .max_retry_attempts(100usize)
+            .min_delay_ms(1000u64)
+            .jitter(JitterMode::None)
+            .max_elapsed(2u64);
+
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let attempts_clone = attempts.clone();
+
+        // Actual
+        let result = retry_with_config::<_, _, (), fn(&anyhow::Error, Duration)>(
+            &config,
+            move || {
+                let attempts = attempts_clone.clone();
+                async move {
+                    attempts.fetch_add(1, Ordering::SeqCst);
+                    Err(Error::Retryable(anyhow::anyhow!("always fails")).into())
+                }
+            },
+            None,
+        )
+        .await;
+
+        // Expected: the elapsed budget trips well before 100 attempts are made.
+        assert!(result.is_err());
+        assert!(attempts.load(Ordering::SeqCst) < 100);
+    }
+
+    #[test]
+    fn test_exponential_backoff_clamps_to_max_delay_instead_of_overflowing() {
+        // Fixture: a factor/min_delay pair that would multiply a Duration well
+        // past its representable range within a handful of attempts if
+        // max_delay weren't wired in to clamp it first.
+        let config = RetryConfig::default()
+            .min_delay_ms(1_000_000u64)
+            .backoff_factor(1_000_000u64)
+            .max_retry_attempts(20usize)
+            .max_delay(5u64);
+
+        // Actual: collecting the whole schedule must not panic.
+        let delays: Vec<Duration> = exponential_backoff(&config).collect();
+
+        // Expected: every delay respects the configured ceiling.
+        assert!(!delays.is_empty());
+        assert!(delays.iter().all(|delay| *delay <= Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_exponential_backoff_grows_unbounded_without_a_max_delay() {
+        // Fixture: no max_delay set, so the schedule should still grow instead
+        // of being clamped to some implicit default.
+        let config = RetryConfig::default()
+            .min_delay_ms(1000u64)
+            .backoff_factor(2u64)
+            .max_retry_attempts(4usize);
+
+        // Actual
+        let delays: Vec<Duration> = exponential_backoff(&config).collect();
+
+        // Expected: strictly increasing, matching the configured factor.
+        for window in delays.windows(2) {
+            assert!(window[1] > window[0]);
+        }
+    }
+
+    #[test]
+    fn test_apply_jitter_none_keeps_delay_unchanged() {
+        // Fixture
+        let delay = Duration::from_millis(800);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        // Actual
+        let actual = apply_jitter(delay, JitterMode::None, &mut rng);
+
+        // Expected
+        assert_eq!(actual, delay);
+    }
+
+    #[test]
+    fn test_apply_jitter_full_stays_within_bounds() {
+        // Fixture
+        let delay = Duration::from_millis(800);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..100 {
+            // Actual
+            let actual = apply_jitter(delay, JitterMode::Full, &mut rng);
+
+            // Expected
+            assert!(actual <= delay);
+        }
+    }
+
+    #[test]
+    fn test_apply_jitter_equal_stays_within_bounds() {
+        // Fixture
+        let delay = Duration::from_millis(800);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..100 {
+            // Actual
+            let actual = apply_jitter(delay, JitterMode::Equal, &mut rng);
+
+            // Expected
+            assert!(actual >= delay / 2 && actual <= delay);
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+
+use derive_setters::Setters;
+use serde::{Deserialize, Serialize};
+
+use crate::Effort;
+
+/// Per-call overrides for a chat request that aren't part of the
+/// conversation `Context` itself. Passed to a provider's
+/// `chat_with_options`, which falls back to its regular streaming `chat()`
+/// behavior for any field left at its default. A provider that doesn't
+/// support a given sampling parameter warns via `tracing` and drops it
+/// rather than failing the request.
+#[derive(Debug, Clone, Serialize, Deserialize, Setters, PartialEq)]
+#[setters(into, strip_option)]
+pub struct ChatOptions {
+    /// Whether to stream the response incrementally. `true` (the default,
+    /// matching the long-standing behavior of `chat()`) requests an SSE
+    /// stream; `false` requests a single non-streaming completion response,
+    /// which is then wrapped in a one-item stream so callers see the same
+    /// return type regardless of which mode was requested.
+    pub stream: bool,
+    /// Sampling temperature. Supported by all providers covered so far.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Nucleus sampling threshold. Supported by all providers covered so
+    /// far.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// Maximum number of tokens to generate. Required by Anthropic, which
+    /// falls back to the provider's own default if left unset here.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u64>,
+    /// Requested reasoning effort for OpenAI's `o`-series reasoning models
+    /// (o1, o3, o4-mini, ...), sent as their native top-level
+    /// `reasoning_effort` field instead of the OpenRouter-style `reasoning`
+    /// object. OpenAI-compatible only; other providers warn and drop it,
+    /// same as `presence_penalty`/`frequency_penalty`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<Effort>,
+    /// Sequences that stop generation when encountered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop: Option<Vec<String>>,
+    /// Seed for deterministic sampling. Not supported by Anthropic, which
+    /// warns and drops it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Penalizes tokens that have already appeared at all. Not supported by
+    /// Anthropic, which warns and drops it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f32>,
+    /// Penalizes tokens proportionally to how often they've already
+    /// appeared. Not supported by Anthropic, which warns and drops it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f32>,
+    /// Opt-in pre-flight check: if the model's `context_length` is known and
+    /// the prompt's estimated token count exceeds it, `chat()` fails fast
+    /// with a `ContextLengthExceeded` error instead of making the request.
+    /// Off by default so existing callers are never surprised by a new
+    /// failure mode.
+    #[serde(default)]
+    pub validate_context_length: bool,
+    /// Opt-in pre-flight check: if the model's `supports_vision` capability
+    /// is known to be `false` and `context` carries an image message,
+    /// `chat()` fails fast with a `VisionNotSupported` error instead of
+    /// sending a request the provider will reject anyway. Off by default,
+    /// same rationale as `validate_context_length`.
+    #[serde(default)]
+    pub validate_vision_support: bool,
+    /// Forces the model to emit JSON, optionally conforming to a schema,
+    /// instead of free-form text. `None` (the default) leaves the response
+    /// unconstrained. Serialized per-provider by whichever
+    /// `chat_with_options` implementation receives it; a provider that
+    /// doesn't support `chat_with_options` at all warns and drops it, same
+    /// as any other unsupported override.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<ChatResponseFormat>,
+    /// Opt-in: classify item-level stream errors as retryable even after the
+    /// first chunk has already been delivered. Off by default, since neither
+    /// provider supports resuming a partial response - retrying a
+    /// mid-stream error re-issues the whole request and duplicates whatever
+    /// content the caller already received. Callers that want that behavior
+    /// anyway (e.g. because they discard partial output on error) can opt in
+    /// here; callers who want a clean reconnect-and-restart instead should
+    /// use `Client::chat_with_restart_info` rather than this flag.
+    #[serde(default)]
+    pub streaming_retry: bool,
+    /// Opt-in: stop reading the stream as soon as a tool call has been fully
+    /// assembled from the streamed deltas, instead of waiting for the
+    /// provider to close the connection. Agent loops that only act on the
+    /// first tool call save the latency and tokens of reading whatever the
+    /// model goes on to emit afterward. Off by default, since some callers
+    /// (e.g. anything relying on `finish_reason` or trailing content after a
+    /// tool call) need the stream to run to completion.
+    #[serde(default)]
+    pub stop_on_tool_call: bool,
+    /// Requests per-token log-probabilities alongside the completion.
+    /// OpenAI-compatible only; Anthropic warns and drops it, same as
+    /// `presence_penalty`/`frequency_penalty`.
+    #[serde(default)]
+    pub logprobs: bool,
+    /// How many of the model's most likely alternative tokens to return
+    /// log-probabilities for at each position (OpenAI accepts `0..=20`).
+    /// Ignored unless `logprobs` is also set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_logprobs: Option<u32>,
+    /// Opt-in: if the prompt's estimated token count exceeds the model's
+    /// known `context_length`, drop messages to fit instead of sending the
+    /// oversized request as-is. `TruncationStrategy::None` (the default)
+    /// leaves `chat()`'s existing behavior untouched. Applied before
+    /// `validate_context_length`'s own check, so the two can be combined:
+    /// truncate first, then hard-fail if it still doesn't fit.
+    #[serde(default)]
+    pub truncation: TruncationStrategy,
+    /// Arbitrary provider-specific fields (e.g. `service_tier`,
+    /// `parallel_tool_calls`, `metadata`) merged into the outgoing request
+    /// JSON just before it's sent, for fields this crate doesn't model yet.
+    /// A key that collides with one the crate already sets is left alone -
+    /// the crate's own value always wins, since it was derived from a
+    /// typed, validated field rather than an arbitrary blob.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extra_body: Option<serde_json::Map<String, serde_json::Value>>,
+    /// A stable user/session identifier for per-request usage attribution,
+    /// e.g. in a provider-side usage dashboard. Sent as OpenAI's top-level
+    /// `user` field and Anthropic's `metadata.user_id` - each provider's own
+    /// way of tagging a request without it appearing in the model's
+    /// context.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Additional free-form key/value tags for usage attribution. Sent
+    /// as-is to providers that accept an arbitrary metadata map
+    /// (OpenAI-compatible only). Anthropic only supports a single `user_id`
+    /// slot under `metadata` (see `user`); every key here is dropped with a
+    /// warning for a provider that doesn't have anywhere to put it.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+}
+
+/// Where to drop messages from when an oversized context needs to be
+/// shrunk to fit a model's context window. See [`ChatOptions::truncation`].
+/// The system message and the latest user message are always preserved, no
+/// matter the strategy - they carry the instructions and the actual
+/// question, and dropping them would make the truncated context answer the
+/// wrong prompt.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TruncationStrategy {
+    /// Don't truncate; behave exactly as if this option wasn't set.
+    #[default]
+    None,
+    /// Drop the oldest messages first (after the preserved system message),
+    /// keeping the most recent conversation turns.
+    DropOldest,
+    /// Drop messages starting from the middle of the conversation outward,
+    /// keeping the earliest turns (for grounding) and the most recent turns
+    /// (for immediate context) intact the longest.
+    DropMiddle,
+}
+
+/// Requested output shape for a chat response. See [`ChatOptions::response_format`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ChatResponseFormat {
+    /// Plain text - no constraint on the response shape.
+    Text,
+    /// Valid JSON, with no constraint on its structure.
+    JsonObject,
+    /// JSON conforming to the given JSON Schema.
+    JsonSchema(serde_json::Value),
+}
+
+impl ChatOptions {
+    /// True if any sampling parameter (`temperature`, `top_p`, `max_tokens`,
+    /// `reasoning_effort`, `stop`, `seed`, the penalties, or `logprobs`) has
+    /// been overridden. Used by providers that only support `chat()`'s
+    /// `Context`-derived parameters to decide whether to warn about a
+    /// dropped override.
+    pub fn has_sampling_overrides(&self) -> bool {
+        self.temperature.is_some()
+            || self.top_p.is_some()
+            || self.max_tokens.is_some()
+            || self.reasoning_effort.is_some()
+            || self.stop.is_some()
+            || self.seed.is_some()
+            || self.presence_penalty.is_some()
+            || self.frequency_penalty.is_some()
+            || self.logprobs
+    }
+}
+
+impl Default for ChatOptions {
+    fn default() -> Self {
+        Self {
+            stream: true,
+            temperature: None,
+            top_p: None,
+            max_tokens: None,
+            reasoning_effort: None,
+            stop: None,
+            seed: None,
+            presence_penalty: None,
+            frequency_penalty: None,
+            validate_context_length: false,
+            validate_vision_support: false,
+            response_format: None,
+            streaming_retry: false,
+            stop_on_tool_call: false,
+            logprobs: false,
+            top_logprobs: None,
+            truncation: TruncationStrategy::None,
+            extra_body: None,
+            user: None,
+            metadata: HashMap::new(),
+        }
+    }
+}
@@ -165,6 +165,10 @@ impl ContextMessage {
             ContextMessage::Image(_) => false,
         }
     }
+
+    pub fn has_image(&self) -> bool {
+        matches!(self, ContextMessage::Image(_))
+    }
 }
 
 fn tool_call_content_char_count(text_message: &TextMessage) -> usize {
@@ -347,6 +351,31 @@ impl Context {
     pub fn token_count(&self) -> usize {
         self.messages.iter().map(|m| m.token_count()).sum()
     }
+
+    /// True if any message in the context carries an image, i.e. requires a
+    /// vision-capable model to serve. Used by [`Client::check_vision_support`]
+    /// to gate the opt-in `ChatOptions::validate_vision_support` pre-flight
+    /// check.
+    pub fn has_image(&self) -> bool {
+        self.messages.iter().any(|m| m.has_image())
+    }
+
+    /// The trailing assistant message's content, if any - an "assistant
+    /// prefill" seeding the start of the completion (Anthropic's term for
+    /// it, though the shape isn't Anthropic-specific: any context ending in
+    /// a non-empty assistant turn instead of a user/tool turn). Only the
+    /// very last message counts; an assistant message anywhere else is a
+    /// prior conversation turn, not a prefill.
+    pub fn assistant_prefill(&self) -> Option<&str> {
+        match self.messages.last()? {
+            ContextMessage::Text(message)
+                if message.role == Role::Assistant && !message.content.is_empty() =>
+            {
+                Some(message.content.as_str())
+            }
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -572,4 +601,38 @@ mod tests {
 
         assert_yaml_snapshot!(actual);
     }
+
+    #[test]
+    fn test_assistant_prefill_returns_trailing_assistant_content() {
+        let fixture = Context::default()
+            .add_message(ContextMessage::user("Write a haiku", None))
+            .add_message(ContextMessage::assistant("Autumn leaves fall", None, None));
+
+        assert_eq!(fixture.assistant_prefill(), Some("Autumn leaves fall"));
+    }
+
+    #[test]
+    fn test_assistant_prefill_ignores_non_trailing_assistant_message() {
+        let fixture = Context::default()
+            .add_message(ContextMessage::assistant("earlier turn", None, None))
+            .add_message(ContextMessage::user("follow-up", None));
+
+        assert_eq!(fixture.assistant_prefill(), None);
+    }
+
+    #[test]
+    fn test_assistant_prefill_ignores_empty_trailing_assistant_message() {
+        let fixture = Context::default()
+            .add_message(ContextMessage::user("Write a haiku", None))
+            .add_message(ContextMessage::assistant("", None, None));
+
+        assert_eq!(fixture.assistant_prefill(), None);
+    }
+
+    #[test]
+    fn test_assistant_prefill_none_for_trailing_user_message() {
+        let fixture = Context::default().add_message(ContextMessage::user("hi", None));
+
+        assert_eq!(fixture.assistant_prefill(), None);
+    }
 }
@@ -57,6 +57,18 @@ pub enum Error {
 
     #[error(transparent)]
     Retryable(anyhow::Error),
+
+    /// A chat stream's underlying connection dropped after at least one
+    /// chunk had already arrived. Carries the content accumulated before the
+    /// drop so callers that exhausted their reconnect budget can still
+    /// decide what to do with it instead of losing it outright.
+    #[error("chat stream was interrupted after producing partial content: {source}")]
+    #[from(skip)]
+    StreamInterrupted {
+        partial_content: String,
+        #[source]
+        source: anyhow::Error,
+    },
 }
 
 pub type Result<A> = std::result::Result<A, Error>;
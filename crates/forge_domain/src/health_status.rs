@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Outcome of a provider health check: whether its endpoint could be reached
+/// at all, whether the configured credentials were accepted, and how long
+/// the check took. `latency` is `None` when the check never got a response
+/// (e.g. it timed out before `reachable` could even be determined).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HealthStatus {
+    /// Whether the provider's endpoint accepted a connection and responded
+    /// at all, regardless of whether the response indicated valid auth.
+    pub reachable: bool,
+    /// Whether the configured credentials were accepted. `false` on a 401,
+    /// and always `false` when `reachable` is `false`.
+    pub authenticated: bool,
+    /// How long the check took to get a response, if it got one.
+    pub latency: Option<Duration>,
+}
@@ -20,4 +20,13 @@ impl Image {
         let content = format!("data:{mime_type};base64,{base64_encoded}");
         Self { url: content, mime_type }
     }
+
+    /// The base64 payload embedded in `url`, if it's a `data:` URI (as
+    /// produced by `new_bytes`/`new_base64` - the only constructors this
+    /// type has). `None` for a genuine external URL, which providers that
+    /// require base64-encoded image data (e.g. Anthropic) can't accept
+    /// directly.
+    pub fn base64_data(&self) -> Option<&str> {
+        self.url.split_once("base64,").map(|(_, data)| data)
+    }
 }
@@ -1,5 +1,6 @@
 mod agent;
 mod attachment;
+mod chat_options;
 mod chat_request;
 mod chat_response;
 mod compact;
@@ -12,6 +13,7 @@ mod env;
 mod error;
 mod event;
 mod file;
+mod health_status;
 mod http_config;
 mod image;
 mod max_tokens;
@@ -20,11 +22,15 @@ mod merge;
 mod message;
 mod model;
 mod point;
+mod pricing;
 mod provider;
+mod raw_sse_event;
 mod reasoning;
 mod result_stream_ext;
 mod retry_config;
 mod shell;
+mod stream_event;
+mod stream_stats;
 mod suggestion;
 mod system_context;
 mod task;
@@ -48,6 +54,7 @@ mod xml;
 
 pub use agent::*;
 pub use attachment::*;
+pub use chat_options::*;
 pub use chat_request::*;
 pub use chat_response::*;
 pub use compact::*;
@@ -60,6 +67,7 @@ pub use env::*;
 pub use error::*;
 pub use event::*;
 pub use file::*;
+pub use health_status::*;
 pub use http_config::*;
 pub use image::*;
 pub use max_tokens::*;
@@ -67,11 +75,15 @@ pub use mcp::*;
 pub use message::*;
 pub use model::*;
 pub use point::*;
+pub use pricing::*;
 pub use provider::*;
+pub use raw_sse_event::*;
 pub use reasoning::*;
 pub use result_stream_ext::*;
 pub use retry_config::*;
 pub use shell::*;
+pub use stream_event::*;
+pub use stream_stats::*;
 pub use suggestion::*;
 pub use system_context::*;
 pub use task::*;
@@ -6,20 +6,27 @@ use strum_macros::EnumString;
 use super::{ToolCall, ToolCallFull};
 use crate::reasoning::{Reasoning, ReasoningFull};
 
-#[derive(Default, Clone, Debug, Serialize, PartialEq)]
+#[derive(Default, Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Usage {
     pub prompt_tokens: usize,
     pub completion_tokens: usize,
     pub total_tokens: usize,
     pub estimated_tokens: usize,
     pub cached_tokens: usize,
+    pub cache_write_tokens: usize,
+    /// Tokens spent on hidden reasoning/thinking before the visible answer,
+    /// for models that bill it separately (e.g. OpenAI's `o`-series
+    /// `completion_tokens_details.reasoning_tokens`). `0` for providers or
+    /// models that don't report it, or that don't do hidden reasoning at
+    /// all.
+    pub reasoning_tokens: usize,
     pub cost: Option<f64>,
 }
 
 /// Represents a message that was received from the LLM provider
 /// NOTE: Tool call messages are part of the larger Response object and not part
 /// of the message.
-#[derive(Default, Clone, Debug, Setters, PartialEq)]
+#[derive(Default, Clone, Debug, Serialize, Deserialize, Setters, PartialEq)]
 #[setters(into, strip_option)]
 pub struct ChatCompletionMessage {
     pub content: Option<Content>,
@@ -28,10 +35,47 @@ pub struct ChatCompletionMessage {
     pub tool_calls: Vec<ToolCall>,
     pub finish_reason: Option<FinishReason>,
     pub usage: Option<Usage>,
+    /// The upstream provider's request ID for this call (OpenAI's
+    /// `x-request-id`, Anthropic's `request-id`), if the provider sent one.
+    /// Useful for support tickets when a conversation needs to be traced
+    /// back to a specific upstream request.
+    pub request_id: Option<String>,
+    /// The provider that actually served this request, as reported by an
+    /// OpenAI-compatible aggregator (e.g. OpenRouter's `provider` response
+    /// field, such as `"OpenAI"` or `"Fireworks"`). `None` for vanilla
+    /// OpenAI and for providers that don't report routing metadata.
+    pub upstream_provider: Option<String>,
+    /// Per-token log-probabilities for this chunk's content, present only
+    /// when [`crate::ChatOptions::logprobs`] was set and the provider
+    /// supports it (OpenAI-compatible only; `None` otherwise).
+    pub logprobs: Option<Vec<TokenLogprob>>,
+    /// OpenAI's backend configuration fingerprint for this response, present
+    /// alongside [`crate::ChatOptions::seed`] so callers can detect a
+    /// backend change that would break determinism across otherwise-identical
+    /// seeded requests. `None` for providers that don't report one.
+    pub system_fingerprint: Option<String>,
+}
+
+/// One sampled token's log-probability, plus the model's next most likely
+/// alternatives at that position. Mirrors the shape of OpenAI's
+/// `logprobs.content[]` entries.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TokenLogprob {
+    pub token: String,
+    pub logprob: f64,
+    pub top_logprobs: Vec<TopLogprob>,
+}
+
+/// One alternative token OpenAI considered at a [`TokenLogprob`]'s position,
+/// requested via [`crate::ChatOptions::top_logprobs`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TopLogprob {
+    pub token: String,
+    pub logprob: f64,
 }
 
 /// Represents partial or full content of a message
-#[derive(Clone, Debug, PartialEq, Eq, From)]
+#[derive(Clone, Debug, PartialEq, Eq, From, Serialize, Deserialize)]
 pub enum Content {
     Part(ContentPart),
     Full(ContentFull),
@@ -85,11 +129,17 @@ pub enum FinishReason {
     #[strum(serialize = "content_filter")]
     ContentFilter,
     /// The model stopped generating output because it made a tool call.
-    #[strum(serialize = "tool_calls")]
-    ToolCalls,
+    #[strum(serialize = "tool_calls", serialize = "tool_use")]
+    ToolUse,
     /// The model stopped generating output normally.
     #[strum(serialize = "stop", serialize = "end_turn")]
     Stop,
+    /// A provider-specific terminal value with no normalized equivalent
+    /// above. Carries the raw value through rather than dropping it, so
+    /// callers can still see why the model stopped even for reasons this
+    /// enum doesn't know about yet.
+    #[strum(default)]
+    Other(String),
 }
 
 impl ChatCompletionMessage {
@@ -122,6 +172,21 @@ impl ChatCompletionMessage {
         self
     }
 
+    pub fn request_id_opt(mut self, request_id: Option<String>) -> Self {
+        self.request_id = request_id;
+        self
+    }
+
+    pub fn upstream_provider_opt(mut self, upstream_provider: Option<String>) -> Self {
+        self.upstream_provider = upstream_provider;
+        self
+    }
+
+    pub fn system_fingerprint_opt(mut self, system_fingerprint: Option<String>) -> Self {
+        self.system_fingerprint = system_fingerprint;
+        self
+    }
+
     pub fn content_part(mut self, content: impl ToString) -> Self {
         self.content = Some(Content::Part(ContentPart(content.to_string())));
         self
@@ -145,6 +210,25 @@ pub struct ChatCompletionMessageFull {
     pub usage: Usage,
 }
 
+impl From<ChatCompletionMessageFull> for ChatCompletionMessage {
+    fn from(full: ChatCompletionMessageFull) -> Self {
+        ChatCompletionMessage {
+            content: (!full.content.is_empty()).then(|| Content::full(full.content)),
+            reasoning: full.reasoning.map(Content::full),
+            reasoning_details: full
+                .reasoning_details
+                .map(|details| vec![Reasoning::Full(details)]),
+            tool_calls: full.tool_calls.into_iter().map(ToolCall::Full).collect(),
+            finish_reason: None,
+            usage: Some(full.usage),
+            request_id: None,
+            upstream_provider: None,
+            logprobs: None,
+            system_fingerprint: None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -165,7 +249,7 @@ mod tests {
         );
         assert_eq!(
             FinishReason::from_str("tool_calls").unwrap(),
-            FinishReason::ToolCalls
+            FinishReason::ToolUse
         );
         assert_eq!(FinishReason::from_str("stop").unwrap(), FinishReason::Stop);
         assert_eq!(
@@ -173,4 +257,12 @@ mod tests {
             FinishReason::Stop
         );
     }
+
+    #[test]
+    fn test_finish_reason_falls_back_to_other_for_unrecognized_values() {
+        assert_eq!(
+            FinishReason::from_str("max_tokens_custom").unwrap(),
+            FinishReason::Other("max_tokens_custom".to_string())
+        );
+    }
 }
@@ -3,7 +3,7 @@ use derive_setters::Setters;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Deserialize, Serialize, Setters)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, Setters)]
 pub struct Model {
     pub id: ModelId,
     pub name: Option<String>,
@@ -15,6 +15,26 @@ pub struct Model {
     pub supports_parallel_tool_calls: Option<bool>,
     /// Whether the model supports reasoning
     pub supports_reasoning: Option<bool>,
+    /// Whether the model accepts image input
+    pub supports_vision: Option<bool>,
+    /// Present when the provider has marked this model for retirement.
+    /// `None` for a model still in normal service, or for a provider that
+    /// doesn't report deprecation metadata at all.
+    pub deprecated: Option<DeprecationInfo>,
+}
+
+/// A provider's notice that a [`Model`] is being retired, parsed from
+/// per-model metadata or a response header. Both fields are optional since
+/// providers that report deprecation at all don't agree on how much detail
+/// to include.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DeprecationInfo {
+    /// When the model stops being served, in whatever format the provider
+    /// sent (e.g. `"2025-06-30"`), kept as-is rather than parsed since it's
+    /// only ever surfaced to a human, never compared against.
+    pub sunset_date: Option<String>,
+    /// The model id the provider suggests migrating to, if any.
+    pub replacement: Option<String>,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
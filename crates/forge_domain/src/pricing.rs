@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ModelId, Usage};
+
+/// Dollar cost per 1,000 tokens for a single model, used by
+/// [`Pricing::estimate_cost`] to turn a [`Usage`] into a dollar figure when a
+/// provider doesn't report `Usage::cost` itself.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPricing {
+    pub input_per_1k: f64,
+    pub output_per_1k: f64,
+}
+
+/// A table of [`ModelPricing`] keyed by model, installed via
+/// `Client::with_pricing` so `estimate_cost` has something to look up.
+/// Unknown models simply have no entry rather than an error, since pricing
+/// changes far more often than the models themselves.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Pricing(HashMap<ModelId, ModelPricing>);
+
+impl Pricing {
+    pub fn new(prices: HashMap<ModelId, ModelPricing>) -> Self {
+        Self(prices)
+    }
+
+    /// Loads a `Pricing` table from a JSON file shaped like
+    /// `{"<model-id>": {"input_per_1k": 0.0, "output_per_1k": 0.0}}`.
+    pub fn from_json_file(path: impl AsRef<std::path::Path>) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Computes the dollar cost of `usage` for `model`, or `None` if `model`
+    /// has no entry in this table.
+    pub fn estimate_cost(&self, model: &ModelId, usage: &Usage) -> Option<f64> {
+        let pricing = self.0.get(model)?;
+        let input_cost = (usage.prompt_tokens as f64 / 1000.0) * pricing.input_per_1k;
+        let output_cost = (usage.completion_tokens as f64 / 1000.0) * pricing.output_per_1k;
+        Some(input_cost + output_cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture_pricing() -> Pricing {
+        Pricing::new(HashMap::from([(
+            ModelId::new("gpt-4"),
+            ModelPricing { input_per_1k: 0.03, output_per_1k: 0.06 },
+        )]))
+    }
+
+    #[test]
+    fn test_estimate_cost_computes_dollar_cost_for_a_known_model() {
+        let pricing = fixture_pricing();
+        let usage = Usage { prompt_tokens: 1000, completion_tokens: 500, ..Default::default() };
+
+        let actual = pricing.estimate_cost(&ModelId::new("gpt-4"), &usage);
+
+        assert_eq!(actual, Some(0.03 + 0.03));
+    }
+
+    #[test]
+    fn test_estimate_cost_returns_none_for_an_unknown_model() {
+        let pricing = fixture_pricing();
+        let usage = Usage { prompt_tokens: 1000, completion_tokens: 500, ..Default::default() };
+
+        let actual = pricing.estimate_cost(&ModelId::new("unknown-model"), &usage);
+
+        assert_eq!(actual, None);
+    }
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// A single upstream SSE frame, parsed only as far as splitting its `event:`
+/// name from its `data:` payload, without being normalized into a
+/// [`crate::ChatCompletionMessage`]. Returned by a provider's `chat_raw` for
+/// advanced callers who need to see provider-specific event types (e.g.
+/// Anthropic's `message_start`) that the crate doesn't model yet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RawSseEvent {
+    /// The SSE `event:` field. Some providers (e.g. OpenAI-compatible ones)
+    /// never set this, in which case it's the SSE spec's default, `"message"`.
+    pub event: String,
+    /// The SSE `data:` field, parsed as JSON. Kept as a [`serde_json::Value`]
+    /// rather than a typed struct, since the whole point is to expose shapes
+    /// the crate doesn't have a type for yet.
+    pub data: serde_json::Value,
+}
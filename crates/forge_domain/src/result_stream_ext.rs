@@ -175,6 +175,8 @@ mod tests {
                     total_tokens: 15,
                     estimated_tokens: 15,
                     cached_tokens: 0,
+                    cache_write_tokens: 0,
+                    reasoning_tokens: 0,
                     cost: None,
                 })),
             Ok(ChatCompletionMessage::default()
@@ -185,6 +187,8 @@ mod tests {
                     total_tokens: 20,
                     estimated_tokens: 20,
                     cached_tokens: 0,
+                    cache_write_tokens: 0,
+                    reasoning_tokens: 0,
                     cost: None,
                 })),
         ];
@@ -205,6 +209,8 @@ mod tests {
                 total_tokens: 20,
                 estimated_tokens: 20,
                 cached_tokens: 0,
+                cache_write_tokens: 0,
+                reasoning_tokens: 0,
                 cost: None,
             },
             reasoning: None,
@@ -254,6 +260,7 @@ mod tests {
             call_id: Some(ToolCallId::new("call_123")),
             name: Some(ToolName::new("test_tool")),
             arguments_part: "invalid json {".to_string(), // Invalid JSON
+            index: None,
         };
 
         let messages = vec![Ok(ChatCompletionMessage::default()
@@ -1,10 +1,47 @@
+use std::collections::HashMap;
+
 use derive_setters::Setters;
 use merge::Merge;
 use serde::{Deserialize, Serialize};
 
+/// Strategy for spreading out retry delays so that many clients hitting the
+/// same rate limit at once don't all retry in lockstep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum JitterMode {
+    /// Use the computed backoff delay as-is.
+    None,
+    /// Uniformly randomize between the computed delay and half of it, per
+    /// the AWS "equal jitter" formula: `delay / 2 + random(0, delay / 2)`.
+    #[default]
+    Equal,
+    /// Uniformly randomize between zero and the computed delay, per the AWS
+    /// "full jitter" formula: `random(0, delay)`.
+    Full,
+}
+
+/// Per-status-code override of the base/max backoff delay, consulted by
+/// [`RetryConfig::backoff_for_status`]. A 429 rate limit, for instance,
+/// often warrants a much longer wait than a transient 503, and the two
+/// shouldn't have to share one global schedule.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct BackoffOverride {
+    /// Minimum delay in milliseconds between retry attempts for this status,
+    /// in place of `RetryConfig::min_delay_ms`.
+    pub min_delay_ms: u64,
+    /// Maximum delay between retries in seconds for this status, in place of
+    /// `RetryConfig::max_delay`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_delay: Option<u64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Merge, Setters, PartialEq)]
 #[setters(into)]
 pub struct RetryConfig {
+    /// How retry delays are randomized to avoid thundering-herd retries
+    #[merge(strategy = crate::merge::std::overwrite)]
+    pub jitter: JitterMode,
+
     /// Initial backoff delay in milliseconds for retry operations
     #[merge(strategy = crate::merge::std::overwrite)]
     pub initial_backoff_ms: u64,
@@ -29,23 +66,89 @@ pub struct RetryConfig {
     /// Maximum delay between retries in seconds
     #[merge(strategy = crate::merge::std::overwrite)]
     pub max_delay: Option<u64>,
+
+    /// Maximum cumulative time in seconds to spend retrying, including
+    /// backoff sleeps, before giving up and returning the last error. `None`
+    /// means no elapsed-time budget, so only `max_retry_attempts` applies.
+    #[merge(strategy = crate::merge::std::overwrite)]
+    pub max_elapsed: Option<u64>,
+
+    /// Status codes to retry in addition to the built-in classification (see
+    /// `forge_provider::retry::is_retryable`), e.g. a provider-specific code
+    /// that isn't one of the standard 408/429/5xx.
+    #[merge(strategy = crate::merge::std::overwrite)]
+    pub additional_retryable_status_codes: Vec<u16>,
+
+    /// Status codes to treat as terminal even though the built-in
+    /// classification would otherwise retry them. Takes precedence over
+    /// `additional_retryable_status_codes` when a code appears in both.
+    #[merge(strategy = crate::merge::std::overwrite)]
+    pub additional_non_retryable_status_codes: Vec<u16>,
+
+    /// Per-status overrides of the base/max backoff delay, e.g. a longer
+    /// wait for 429 than for a transient 503. Consulted by
+    /// `forge_provider::retry::into_retry` via
+    /// [`RetryConfig::backoff_for_status`]; a status with no entry here
+    /// falls back to `min_delay_ms`/`max_delay`.
+    #[merge(strategy = crate::merge::hashmap)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub per_status_backoff: HashMap<u16, BackoffOverride>,
+
+    /// Substrings that mark a `200 OK` stream body as an in-band error from a
+    /// buggy gateway (e.g. `"upstream timeout"` in `{"error": "upstream
+    /// timeout"}`), which would otherwise bypass status-code-based retry.
+    /// Checked via [`RetryConfig::matched_body_pattern`]. Empty by default,
+    /// since matching legitimate content that happens to mention "error" is
+    /// worse than missing a retry - callers must opt a gateway's known
+    /// failure strings in explicitly.
+    #[merge(strategy = crate::merge::std::overwrite)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub retry_on_body_patterns: Vec<String>,
 }
 
 impl Default for RetryConfig {
     fn default() -> Self {
         Self {
+            jitter: JitterMode::default(),
             initial_backoff_ms: 200,
             min_delay_ms: 1000,
             backoff_factor: 2,
             max_retry_attempts: 8,
             retry_status_codes: vec![429, 500, 502, 503, 504],
             max_delay: None,
+            max_elapsed: None,
+            additional_retryable_status_codes: Vec::new(),
+            additional_non_retryable_status_codes: Vec::new(),
+            per_status_backoff: HashMap::new(),
+            retry_on_body_patterns: Vec::new(),
         }
     }
 }
 
 impl RetryConfig {
-    // Implementation moved to forge_app::retry module to avoid backon dependency
+    // Backoff-loop implementation moved to forge_app::retry module to avoid
+    // backon dependency; this stays a pure lookup so forge_provider (which
+    // doesn't depend on backon) can call it from `into_retry` too.
+
+    /// The base/max delay to use for a retry triggered by `status`: the
+    /// matching entry in `per_status_backoff` if one exists, otherwise the
+    /// global `min_delay_ms`/`max_delay`.
+    pub fn backoff_for_status(&self, status: u16) -> (u64, Option<u64>) {
+        match self.per_status_backoff.get(&status) {
+            Some(override_) => (override_.min_delay_ms, override_.max_delay),
+            None => (self.min_delay_ms, self.max_delay),
+        }
+    }
+
+    /// The first entry of `retry_on_body_patterns` that appears as a
+    /// substring of `body`, if any. `None` when the list is empty (the
+    /// default) or nothing matches.
+    pub fn matched_body_pattern(&self, body: &str) -> Option<&str> {
+        self.retry_on_body_patterns
+            .iter()
+            .find(|pattern| body.contains(pattern.as_str()))
+            .map(String::as_str)
+    }
 }
 
 #[cfg(test)]
@@ -65,6 +168,9 @@ mod tests {
         assert_eq!(config.backoff_factor, 2);
         assert_eq!(config.max_retry_attempts, 8);
         assert_eq!(config.retry_status_codes, vec![429, 500, 502, 503, 504]);
+        assert_eq!(config.jitter, JitterMode::Equal);
+        assert_eq!(config.max_delay, None);
+        assert_eq!(config.max_elapsed, None);
     }
 
     #[test]
@@ -75,7 +181,8 @@ mod tests {
             .min_delay_ms(500u64)
             .backoff_factor(3u64)
             .max_retry_attempts(5usize)
-            .retry_status_codes(vec![429, 503]);
+            .retry_status_codes(vec![429, 503])
+            .max_elapsed(30u64);
 
         // Expected: Should have custom values
         assert_eq!(config.initial_backoff_ms, 100);
@@ -83,5 +190,76 @@ mod tests {
         assert_eq!(config.backoff_factor, 3);
         assert_eq!(config.max_retry_attempts, 5);
         assert_eq!(config.retry_status_codes, vec![429, 503]);
+        assert_eq!(config.max_elapsed, Some(30));
+    }
+
+    #[test]
+    fn test_backoff_for_status_falls_back_to_global_when_no_override_matches() {
+        // Fixture
+        let config = RetryConfig::default().min_delay_ms(1000u64).max_delay(30u64);
+
+        // Actual
+        let actual = config.backoff_for_status(503);
+
+        // Expected
+        assert_eq!(actual, (1000, Some(30)));
+    }
+
+    #[test]
+    fn test_backoff_for_status_uses_matching_override() {
+        // Fixture
+        let mut per_status_backoff = HashMap::new();
+        per_status_backoff.insert(429, BackoffOverride { min_delay_ms: 5000, max_delay: Some(60) });
+        per_status_backoff.insert(503, BackoffOverride { min_delay_ms: 500, max_delay: Some(10) });
+        let config = RetryConfig::default()
+            .min_delay_ms(1000u64)
+            .max_delay(30u64)
+            .per_status_backoff(per_status_backoff);
+
+        // Actual
+        let for_429 = config.backoff_for_status(429);
+        let for_503 = config.backoff_for_status(503);
+
+        // Expected: distinct overrides produce distinct computed delays.
+        assert_eq!(for_429, (5000, Some(60)));
+        assert_eq!(for_503, (500, Some(10)));
+        assert_ne!(for_429, for_503);
+    }
+
+    #[test]
+    fn test_matched_body_pattern_is_none_by_default() {
+        // Fixture
+        let config = RetryConfig::default();
+
+        // Actual
+        let actual = config.matched_body_pattern(r#"{"error": "upstream timeout"}"#);
+
+        // Expected: no patterns configured, so nothing ever matches.
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn test_matched_body_pattern_finds_configured_substring() {
+        // Fixture
+        let config = RetryConfig::default()
+            .retry_on_body_patterns(vec!["upstream timeout".to_string(), "gateway busy".to_string()]);
+
+        // Actual
+        let actual = config.matched_body_pattern(r#"{"error": "upstream timeout"}"#);
+
+        // Expected
+        assert_eq!(actual, Some("upstream timeout"));
+    }
+
+    #[test]
+    fn test_matched_body_pattern_does_not_match_unrelated_content() {
+        // Fixture: legitimate content that happens to contain the word "error"
+        let config = RetryConfig::default().retry_on_body_patterns(vec!["upstream timeout".to_string()]);
+
+        // Actual
+        let actual = config.matched_body_pattern("The word error appears in this sentence.");
+
+        // Expected
+        assert_eq!(actual, None);
     }
 }
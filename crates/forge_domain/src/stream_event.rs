@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ChatCompletionMessage;
+
+/// A single item from a keepalive-aware chat stream, distinguishing real
+/// content from a provider's heartbeat frames (e.g. Anthropic's `ping`
+/// event, sent to hold the connection open during a long tool execution on
+/// the server side). `chat()`'s normal stream already drops heartbeats
+/// silently; this is for callers that want to know a heartbeat arrived
+/// (e.g. to keep showing a "thinking..." indicator) without it being
+/// mistaken for an empty content chunk.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum StreamEvent {
+    /// A normalized chat completion chunk, same as `chat()` yields.
+    Content(ChatCompletionMessage),
+    /// A heartbeat frame with no content of its own. Purely informational -
+    /// nothing needs to be appended to the response.
+    KeepAlive,
+}
@@ -0,0 +1,102 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Lock-free running totals for a chat stream, shared between the stream
+/// consumer and whatever's rendering a progress indicator alongside it (see
+/// `Client::chat_with_stats`). Every method is a plain atomic load/store, so
+/// a UI can poll it from another task without contending with the stream
+/// itself.
+#[derive(Debug)]
+pub struct StreamStats {
+    chunks: AtomicUsize,
+    bytes: AtomicU64,
+    estimated_tokens: AtomicUsize,
+    started: Instant,
+}
+
+impl StreamStats {
+    pub fn new() -> Self {
+        Self {
+            chunks: AtomicUsize::new(0),
+            bytes: AtomicU64::new(0),
+            estimated_tokens: AtomicUsize::new(0),
+            started: Instant::now(),
+        }
+    }
+
+    /// Folds one delivered chunk's content into the running totals. Called
+    /// once per stream item as it's consumed.
+    pub fn record_chunk(&self, content_len: usize) {
+        self.chunks.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(content_len as u64, Ordering::Relaxed);
+        self.estimated_tokens
+            .fetch_add(crate::estimate_token_count(content_len), Ordering::Relaxed);
+    }
+
+    pub fn chunks(&self) -> usize {
+        self.chunks.load(Ordering::Relaxed)
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.bytes.load(Ordering::Relaxed)
+    }
+
+    pub fn estimated_tokens(&self) -> usize {
+        self.estimated_tokens.load(Ordering::Relaxed)
+    }
+
+    /// Wall-clock time since this `StreamStats` was created, i.e. since the
+    /// stream started being consumed.
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Bytes delivered per second of elapsed time so far. `0.0` before the
+    /// first chunk has had a chance to move the clock forward.
+    pub fn bytes_per_second(&self) -> f64 {
+        let elapsed = self.elapsed().as_secs_f64();
+        if elapsed <= 0.0 { 0.0 } else { self.bytes() as f64 / elapsed }
+    }
+}
+
+impl Default for StreamStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_chunk_accumulates_chunks_and_bytes() {
+        let fixture = StreamStats::new();
+
+        fixture.record_chunk(5);
+        fixture.record_chunk(10);
+
+        assert_eq!(fixture.chunks(), 2);
+        assert_eq!(fixture.bytes(), 15);
+    }
+
+    #[test]
+    fn test_record_chunk_accumulates_estimated_tokens() {
+        let fixture = StreamStats::new();
+
+        fixture.record_chunk(8);
+        fixture.record_chunk(8);
+
+        assert_eq!(fixture.estimated_tokens(), crate::estimate_token_count(8) * 2);
+    }
+
+    #[test]
+    fn test_new_stats_start_at_zero() {
+        let fixture = StreamStats::new();
+
+        assert_eq!(fixture.chunks(), 0);
+        assert_eq!(fixture.bytes(), 0);
+        assert_eq!(fixture.estimated_tokens(), 0);
+        assert_eq!(fixture.bytes_per_second(), 0.0);
+    }
+}
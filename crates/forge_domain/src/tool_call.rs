@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use derive_more::derive::From;
 use derive_setters::Setters;
 use serde::{Deserialize, Serialize};
@@ -39,6 +41,12 @@ pub struct ToolCallPart {
     /// Arguments that need to be passed to the tool. NOTE: Not all tools
     /// require input
     pub arguments_part: String,
+
+    /// The position of the tool call within the response, as reported by the
+    /// provider. Needed to reassemble parts into full tool calls when
+    /// multiple tool calls stream interleaved with one another; providers
+    /// that never interleave (and so never need it) leave this `None`.
+    pub index: Option<usize>,
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, From)]
@@ -84,6 +92,10 @@ impl ToolCallFull {
             return Ok(vec![]);
         }
 
+        if parts.iter().any(|part| part.index.is_some()) {
+            return Self::try_from_indexed_parts(parts);
+        }
+
         let mut tool_name: Option<&ToolName> = None;
         let mut tool_call_id = None;
 
@@ -129,6 +141,51 @@ impl ToolCallFull {
         Ok(tool_calls)
     }
 
+    /// Reassembles parts that carry a provider-reported `index`, grouping by
+    /// that index instead of assuming parts for the same tool call arrive
+    /// contiguously. This is what makes parallel tool calls that stream
+    /// interleaved (e.g. OpenAI's `tool_calls[].index` deltas) reassemble
+    /// correctly.
+    fn try_from_indexed_parts(parts: &[ToolCallPart]) -> Result<Vec<Self>> {
+        let mut order = Vec::new();
+        let mut groups: HashMap<usize, (Option<ToolName>, Option<ToolCallId>, String)> =
+            HashMap::new();
+
+        for part in parts.iter() {
+            let index = part.index.unwrap_or_default();
+            let group = groups.entry(index).or_insert_with(|| {
+                order.push(index);
+                (None, None, String::new())
+            });
+
+            if let Some(name) = &part.name {
+                group.0 = Some(name.clone());
+            }
+            if let Some(call_id) = &part.call_id {
+                group.1 = Some(call_id.clone());
+            }
+            group.2.push_str(&part.arguments_part);
+        }
+
+        order
+            .into_iter()
+            .map(|index| {
+                let (name, call_id, arguments) = groups
+                    .remove(&index)
+                    .expect("every collected index has a group");
+                Ok(ToolCallFull {
+                    name: name.ok_or(Error::ToolCallMissingName)?,
+                    call_id,
+                    arguments: if arguments.is_empty() {
+                        Value::default()
+                    } else {
+                        serde_json::from_str(&arguments).map_err(Error::ToolCallArgument)?
+                    },
+                })
+            })
+            .collect()
+    }
+
     /// Parse multiple tool calls from XML format.
     pub fn try_from_xml(input: &str) -> std::result::Result<Vec<ToolCallFull>, Error> {
         match extract_tag_content(input, "forge_tool_call") {
@@ -160,31 +217,37 @@ mod tests {
                 call_id: Some(ToolCallId("call_1".to_string())),
                 name: Some(ToolName::new("forge_tool_fs_read")),
                 arguments_part: "{\"path\": \"crates/forge_services/src/fixtures/".to_string(),
+                index: None,
             },
             ToolCallPart {
                 call_id: None,
                 name: None,
                 arguments_part: "mascot.md\"}".to_string(),
+                index: None,
             },
             ToolCallPart {
                 call_id: Some(ToolCallId("call_2".to_string())),
                 name: Some(ToolName::new("forge_tool_fs_read")),
                 arguments_part: "{\"path\": \"docs/".to_string(),
+                index: None,
             },
             ToolCallPart {
                 call_id: None,
                 name: None,
                 arguments_part: "onboarding.md\"}".to_string(),
+                index: None,
             },
             ToolCallPart {
                 call_id: Some(ToolCallId("call_3".to_string())),
                 name: Some(ToolName::new("forge_tool_fs_read")),
                 arguments_part: "{\"path\": \"crates/forge_services/src/service/".to_string(),
+                index: None,
             },
             ToolCallPart {
                 call_id: None,
                 name: None,
                 arguments_part: "service.md\"}".to_string(),
+                index: None,
             },
         ];
 
@@ -217,6 +280,7 @@ mod tests {
             call_id: Some(ToolCallId("call_1".to_string())),
             name: Some(ToolName::new("forge_tool_fs_read")),
             arguments_part: "{\"path\": \"docs/onboarding.md\"}".to_string(),
+            index: None,
         }];
 
         let actual = ToolCallFull::try_from_parts(&input).unwrap();
@@ -243,6 +307,7 @@ mod tests {
             call_id: Some(ToolCallId("call_1".to_string())),
             name: Some(ToolName::new("screenshot")),
             arguments_part: "".to_string(),
+            index: None,
         }];
 
         let actual = ToolCallFull::try_from_parts(&input).unwrap();
@@ -255,6 +320,53 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_interleaved_parallel_calls_reassemble_by_index() {
+        let input = [
+            ToolCallPart {
+                call_id: Some(ToolCallId("call_1".to_string())),
+                name: Some(ToolName::new("forge_tool_fs_read")),
+                arguments_part: "{\"path\": \"a.md\"".to_string(),
+                index: Some(0),
+            },
+            ToolCallPart {
+                call_id: Some(ToolCallId("call_2".to_string())),
+                name: Some(ToolName::new("forge_tool_fs_read")),
+                arguments_part: "{\"path\": \"b.md\"".to_string(),
+                index: Some(1),
+            },
+            ToolCallPart {
+                call_id: None,
+                name: None,
+                arguments_part: "}".to_string(),
+                index: Some(0),
+            },
+            ToolCallPart {
+                call_id: None,
+                name: None,
+                arguments_part: "}".to_string(),
+                index: Some(1),
+            },
+        ];
+
+        let actual = ToolCallFull::try_from_parts(&input).unwrap();
+
+        let expected = vec![
+            ToolCallFull {
+                name: ToolName::new("forge_tool_fs_read"),
+                call_id: Some(ToolCallId("call_1".to_string())),
+                arguments: serde_json::json!({"path": "a.md"}),
+            },
+            ToolCallFull {
+                name: ToolName::new("forge_tool_fs_read"),
+                call_id: Some(ToolCallId("call_2".to_string())),
+                arguments: serde_json::json!({"path": "b.md"}),
+            },
+        ];
+
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_real_example() {
         let message = include_str!("./fixtures/tool_call_01.md");
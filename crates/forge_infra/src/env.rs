@@ -80,9 +80,14 @@ impl ForgeEnvironmentInfra {
                 config.connect_timeout = parsed;
             }
         }
-        if let Ok(val) = std::env::var("FORGE_HTTP_READ_TIMEOUT") {
+        if let Ok(val) = std::env::var("FORGE_HTTP_FIRST_TOKEN_TIMEOUT") {
             if let Ok(parsed) = val.parse::<u64>() {
-                config.read_timeout = parsed;
+                config.first_token_timeout = parsed;
+            }
+        }
+        if let Ok(val) = std::env::var("FORGE_HTTP_INTER_TOKEN_TIMEOUT") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                config.inter_token_timeout = parsed;
             }
         }
         if let Ok(val) = std::env::var("FORGE_HTTP_POOL_IDLE_TIMEOUT") {
@@ -100,6 +105,43 @@ impl ForgeEnvironmentInfra {
                 config.max_redirects = parsed;
             }
         }
+        if let Ok(val) = std::env::var("FORGE_HTTP_PROXY") {
+            config.http_proxy = Some(val);
+        }
+        if let Ok(val) = std::env::var("FORGE_HTTPS_PROXY") {
+            config.https_proxy = Some(val);
+        }
+        if let Ok(val) = std::env::var("FORGE_NO_PROXY") {
+            config.no_proxy = Some(val);
+        }
+        if let Ok(val) = std::env::var("FORGE_HTTP_USER_AGENT") {
+            config.user_agent = Some(val);
+        }
+        if let Ok(val) = std::env::var("FORGE_HTTP_MAX_REQUEST_BYTES") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                config.max_request_bytes = Some(parsed);
+            }
+        }
+        if let Ok(val) = std::env::var("FORGE_HTTP2_PRIOR_KNOWLEDGE") {
+            if let Ok(parsed) = val.parse::<bool>() {
+                config.http2_prior_knowledge = parsed;
+            }
+        }
+        if let Ok(val) = std::env::var("FORGE_HTTP2_KEEP_ALIVE_INTERVAL") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                config.http2_keep_alive_interval = Some(std::time::Duration::from_secs(parsed));
+            }
+        }
+        if let Ok(val) = std::env::var("FORGE_HTTP_FORCE_HTTP1") {
+            if let Ok(parsed) = val.parse::<bool>() {
+                config.force_http1 = parsed;
+            }
+        }
+        if let Ok(val) = std::env::var("FORGE_HTTP_ENABLE_COMPRESSION") {
+            if let Ok(parsed) = val.parse::<bool>() {
+                config.enable_compression = parsed;
+            }
+        }
 
         config
     }
@@ -392,7 +434,8 @@ mod tests {
     fn test_http_config_environment_variables() {
         // Clean up any existing environment variables first
         env::remove_var("FORGE_HTTP_CONNECT_TIMEOUT");
-        env::remove_var("FORGE_HTTP_READ_TIMEOUT");
+        env::remove_var("FORGE_HTTP_FIRST_TOKEN_TIMEOUT");
+        env::remove_var("FORGE_HTTP_INTER_TOKEN_TIMEOUT");
         env::remove_var("FORGE_HTTP_POOL_IDLE_TIMEOUT");
         env::remove_var("FORGE_HTTP_POOL_MAX_IDLE_PER_HOST");
         env::remove_var("FORGE_HTTP_MAX_REDIRECTS");
@@ -404,7 +447,8 @@ mod tests {
             let default_config = forge_domain::HttpConfig::default();
 
             assert_eq!(config.connect_timeout, default_config.connect_timeout);
-            assert_eq!(config.read_timeout, default_config.read_timeout);
+            assert_eq!(config.first_token_timeout, default_config.first_token_timeout);
+            assert_eq!(config.inter_token_timeout, default_config.inter_token_timeout);
             assert_eq!(config.pool_idle_timeout, default_config.pool_idle_timeout);
             assert_eq!(
                 config.pool_max_idle_per_host,
@@ -416,7 +460,8 @@ mod tests {
         // Test environment variable overrides
         {
             env::set_var("FORGE_HTTP_CONNECT_TIMEOUT", "30");
-            env::set_var("FORGE_HTTP_READ_TIMEOUT", "120");
+            env::set_var("FORGE_HTTP_FIRST_TOKEN_TIMEOUT", "45");
+            env::set_var("FORGE_HTTP_INTER_TOKEN_TIMEOUT", "120");
             env::set_var("FORGE_HTTP_POOL_IDLE_TIMEOUT", "180");
             env::set_var("FORGE_HTTP_POOL_MAX_IDLE_PER_HOST", "10");
             env::set_var("FORGE_HTTP_MAX_REDIRECTS", "20");
@@ -425,14 +470,16 @@ mod tests {
             let config = env_service.resolve_timeout_config();
 
             assert_eq!(config.connect_timeout, 30);
-            assert_eq!(config.read_timeout, 120);
+            assert_eq!(config.first_token_timeout, 45);
+            assert_eq!(config.inter_token_timeout, 120);
             assert_eq!(config.pool_idle_timeout, 180);
             assert_eq!(config.pool_max_idle_per_host, 10);
             assert_eq!(config.max_redirects, 20);
 
             // Clean up environment variables
             env::remove_var("FORGE_HTTP_CONNECT_TIMEOUT");
-            env::remove_var("FORGE_HTTP_READ_TIMEOUT");
+            env::remove_var("FORGE_HTTP_FIRST_TOKEN_TIMEOUT");
+            env::remove_var("FORGE_HTTP_INTER_TOKEN_TIMEOUT");
             env::remove_var("FORGE_HTTP_POOL_IDLE_TIMEOUT");
             env::remove_var("FORGE_HTTP_POOL_MAX_IDLE_PER_HOST");
             env::remove_var("FORGE_HTTP_MAX_REDIRECTS");
@@ -450,7 +497,8 @@ mod tests {
             assert_eq!(config.connect_timeout, 15);
 
             // Default values should remain
-            assert_eq!(config.read_timeout, default_config.read_timeout);
+            assert_eq!(config.first_token_timeout, default_config.first_token_timeout);
+            assert_eq!(config.inter_token_timeout, default_config.inter_token_timeout);
             assert_eq!(config.pool_idle_timeout, default_config.pool_idle_timeout);
             assert_eq!(
                 config.pool_max_idle_per_host,
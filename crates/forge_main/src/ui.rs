@@ -920,6 +920,8 @@ mod tests {
             tools_supported,
             supports_parallel_tool_calls: None,
             supports_reasoning: None,
+            supports_vision: None,
+            deprecated: None,
         }
     }
 
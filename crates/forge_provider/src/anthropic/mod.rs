@@ -3,3 +3,9 @@ mod request;
 mod response;
 mod transforms;
 pub use provider::Anthropic;
+// Bedrock's Claude models speak the same Anthropic Messages API wire format,
+// so the `bedrock` module reuses these instead of duplicating them.
+#[cfg(feature = "bedrock")]
+pub(crate) use request::Request as AnthropicRequest;
+#[cfg(feature = "bedrock")]
+pub(crate) use response::EventData as AnthropicEventData;
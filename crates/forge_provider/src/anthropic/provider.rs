@@ -1,26 +1,73 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
 use anyhow::Context as _;
 use derive_builder::Builder;
 use forge_app::domain::{
-    ChatCompletionMessage, Context, Model, ModelId, ResultStream, Transformer,
+    ChatCompletionMessage, ChatOptions, ChatResponseFormat, Context, Model, ModelId, RawSseEvent,
+    ResultStream, StreamEvent, Transformer,
 };
-use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use reqwest::{Client, Url};
 use reqwest_eventsource::{Event, RequestBuilderExt};
 use tokio_stream::StreamExt;
 use tracing::debug;
 
-use super::request::Request;
-use super::response::{EventData, ListModelResponse};
-use crate::anthropic::transforms::ReasoningTransform;
+use super::request::{Metadata, Request, ToolChoice, ToolDefinition, STRUCTURED_OUTPUT_TOOL_NAME};
+use super::response::{EventData, ListModelResponse, MessageResponse};
+use crate::anthropic::transforms::{ReasoningTransform, SetCache};
 use crate::error::Error;
-use crate::utils::format_http_context;
+use crate::streaming_timeout::with_token_timeouts;
+use crate::utils::{
+    extract_request_id, format_http_context, headers_to_json, join_base_url,
+    normalize_stop_sequences, sanitize_headers, serialize_with_size_guard, with_request_id_context,
+};
+
+/// Anthropic doesn't document a hard cap on `stop_sequences` the way OpenAI
+/// does for `stop`; this is a generous sanity ceiling to catch obviously
+/// malformed input (e.g. a caller accidentally passing one sequence per
+/// character) rather than a documented API limit.
+const MAX_STOP_SEQUENCES: usize = 100;
 
 #[derive(Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct Anthropic {
     client: Client,
     api_key: String,
     base_url: Url,
     anthropic_version: String,
+    extra_headers: Option<HashMap<String, String>>,
+    /// How long to wait for the first streamed chunk after the request is
+    /// sent, before failing with [`crate::error::ProviderError::FirstTokenTimeout`].
+    first_token_timeout: Duration,
+    /// How long to wait for each subsequent chunk once the first has
+    /// arrived, reset on every chunk, before failing with
+    /// [`crate::error::ProviderError::InterTokenTimeout`].
+    inter_token_timeout: Duration,
+    /// Maximum size, in bytes, of a serialized chat request body. `None`
+    /// (the default) disables the check. See
+    /// [`crate::error::ProviderError::RequestTooLarge`].
+    max_request_bytes: Option<u64>,
+}
+
+impl AnthropicBuilder {
+    /// Rejects `extra_headers` that aren't valid HTTP header names/values
+    /// before the client is ever built, so a misconfigured header shows up
+    /// as a construction error instead of failing silently on every request.
+    fn validate(&self) -> Result<(), String> {
+        let Some(Some(headers)) = &self.extra_headers else {
+            return Ok(());
+        };
+
+        for (name, value) in headers {
+            HeaderName::from_bytes(name.as_bytes())
+                .map_err(|err| format!("Invalid extra header name `{name}`: {err}"))?;
+            HeaderValue::from_str(value)
+                .map_err(|err| format!("Invalid extra header value for `{name}`: {err}"))?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Anthropic {
@@ -29,17 +76,7 @@ impl Anthropic {
     }
 
     fn url(&self, path: &str) -> anyhow::Result<Url> {
-        // Validate the path doesn't contain certain patterns
-        if path.contains("://") || path.contains("..") {
-            anyhow::bail!("Invalid path: Contains forbidden patterns");
-        }
-
-        // Remove leading slash to avoid double slashes
-        let path = path.trim_start_matches('/');
-
-        self.base_url
-            .join(path)
-            .with_context(|| format!("Failed to append {} to base URL: {}", path, self.base_url))
+        join_base_url(&self.base_url, path)
     }
 
     fn headers(&self) -> HeaderMap {
@@ -56,24 +93,354 @@ impl Anthropic {
             "anthropic-version",
             HeaderValue::from_str(&self.anthropic_version).unwrap(),
         );
+
+        // note: validated in `AnthropicBuilder::validate`, so these are known-good.
+        if let Some(extra_headers) = &self.extra_headers {
+            for (name, value) in extra_headers {
+                headers.insert(
+                    HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                    HeaderValue::from_str(value).unwrap(),
+                );
+            }
+        }
+
         headers
     }
 }
 
 impl Anthropic {
-    pub async fn chat(
+    fn build_request(
         &self,
         model: &ModelId,
         context: Context,
-    ) -> ResultStream<ChatCompletionMessage, anyhow::Error> {
-        let max_tokens = context.max_tokens.unwrap_or(4000);
+        options: &ChatOptions,
+    ) -> anyhow::Result<Request> {
+        let max_tokens = options
+            .max_tokens
+            .or(context.max_tokens.map(|t| t as u64))
+            .unwrap_or(4000);
         // transform the context to match the request format
         let context = ReasoningTransform.transform(context);
 
-        let request = Request::try_from(context)?
+        let request = Request::try_from(context)?;
+        let mut request = SetCache
+            .transform(request)
             .model(model.as_str().to_string())
-            .stream(true)
-            .max_tokens(max_tokens as u64);
+            .stream(options.stream)
+            .max_tokens(max_tokens);
+
+        if let Some(temperature) = options.temperature {
+            request = request.temperature(temperature);
+        }
+        if let Some(top_p) = options.top_p {
+            request = request.top_p(top_p);
+        }
+        if let Some(stop) = normalize_stop_sequences(options.stop.as_deref(), MAX_STOP_SEQUENCES)?
+        {
+            request = request.stop_sequences(stop);
+        }
+        if options.reasoning_effort.is_some() {
+            tracing::warn!("Anthropic does not support `reasoning_effort`; dropping it");
+        }
+        if options.seed.is_some() {
+            tracing::warn!("Anthropic does not support `seed`; dropping it");
+        }
+        if options.presence_penalty.is_some() {
+            tracing::warn!("Anthropic does not support `presence_penalty`; dropping it");
+        }
+        if options.frequency_penalty.is_some() {
+            tracing::warn!("Anthropic does not support `frequency_penalty`; dropping it");
+        }
+        if options.logprobs {
+            tracing::warn!("Anthropic does not support `logprobs`; dropping it");
+        }
+        if let Some(user) = &options.user {
+            request = request.metadata(Metadata::with_user_id(user.clone()));
+        }
+        if !options.metadata.is_empty() {
+            tracing::warn!(
+                "Anthropic's request metadata only supports `user`; dropping the rest of \
+                 `ChatOptions::metadata`"
+            );
+        }
+
+        // Anthropic has no native `response_format` parameter, so JSON mode is
+        // enforced via the tool-use trick: force a single call to a synthetic
+        // tool whose `input_schema` mirrors the desired JSON shape.
+        let json_schema = match &options.response_format {
+            None | Some(ChatResponseFormat::Text) => None,
+            Some(ChatResponseFormat::JsonObject) => Some(serde_json::json!({ "type": "object" })),
+            Some(ChatResponseFormat::JsonSchema(schema)) => Some(schema.clone()),
+        };
+        if let Some(schema) = json_schema {
+            request = request
+                .add_tool(ToolDefinition::structured_output(schema))
+                .tool_choice(ToolChoice::Tool {
+                    name: STRUCTURED_OUTPUT_TOOL_NAME.to_string(),
+                    disable_parallel_tool_use: None,
+                });
+        }
+
+        Ok(request)
+    }
+
+    /// Builds the exact JSON body and headers `chat()` would send for
+    /// `model`/`context`, without performing any I/O. Useful for diagnosing
+    /// why a provider rejects a payload, since it reflects the same
+    /// serialization and transformer pipeline `chat()` uses.
+    pub fn build_chat_request(
+        &self,
+        model: &ModelId,
+        context: Context,
+    ) -> anyhow::Result<serde_json::Value> {
+        let request = self.build_request(model, context, &ChatOptions::default())?;
+        let url = self.url("/messages")?;
+        let headers = sanitize_headers(&self.headers());
+
+        Ok(serde_json::json!({
+            "url": url.to_string(),
+            "headers": headers_to_json(&headers),
+            "body": request,
+        }))
+    }
+
+    pub async fn chat(
+        &self,
+        model: &ModelId,
+        context: Context,
+    ) -> ResultStream<ChatCompletionMessage, anyhow::Error> {
+        self.chat_with_options(model, context, ChatOptions::default()).await
+    }
+
+    pub async fn chat_with_options(
+        &self,
+        model: &ModelId,
+        context: Context,
+        options: ChatOptions,
+    ) -> ResultStream<ChatCompletionMessage, anyhow::Error> {
+        if options.stream {
+            self.inner_chat(model, context, &options).await
+        } else {
+            self.inner_chat_once(model, context, &options).await
+        }
+    }
+
+    /// Sends `"stream": false` and parses Anthropic's single non-streaming
+    /// `/messages` response instead of consuming an SSE stream, wrapping the
+    /// result in a one-item stream so callers see the same return type
+    /// regardless of which mode was requested.
+    async fn inner_chat_once(
+        &self,
+        model: &ModelId,
+        context: Context,
+        options: &ChatOptions,
+    ) -> ResultStream<ChatCompletionMessage, anyhow::Error> {
+        let request = self.build_request(model, context, options)?;
+        let body = serialize_with_size_guard(
+            &request,
+            self.max_request_bytes,
+            options.extra_body.as_ref(),
+        )?;
+
+        let url = self.url("/messages")?;
+        debug!(url = %url, model = %model, "Connecting Upstream (non-streaming)");
+
+        let response = self
+            .client
+            .post(url.clone())
+            .headers(self.headers())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await
+            .with_context(|| format_http_context(None, "POST", &url))?;
+
+        let status = response.status();
+        let request_id = extract_request_id(response.headers());
+        let body = response
+            .text()
+            .await
+            .with_context(|| format_http_context(Some(status), "POST", &url))
+            .with_context(|| "Failed to decode response into text")?;
+
+        let message = if status.is_success() {
+            serde_json::from_str::<MessageResponse>(&body)
+                .with_context(|| "Failed to parse Anthropic response")
+                .and_then(|response| {
+                    ChatCompletionMessage::try_from(response)
+                        .with_context(|| format!("Failed to create completion message: {body}"))
+                })
+                .map(|message| message.request_id_opt(request_id.clone()))
+        } else {
+            Err(Error::InvalidStatusCode(status.as_u16())).with_context(|| {
+                format!(
+                    "Invalid status code: {status} Reason: {}",
+                    crate::error::describe_error_body(&body)
+                )
+            })
+        };
+        let message = with_request_id_context(message, request_id.as_deref())
+            .with_context(|| format_http_context(None, "POST", &url));
+
+        Ok(Box::pin(tokio_stream::once(message)))
+    }
+
+    /// Like `chat()`, but yields the SSE frames as-received - `event:` name
+    /// plus `data:` payload - instead of normalizing them into
+    /// [`ChatCompletionMessage`]. Lets advanced callers see event types this
+    /// crate doesn't model yet (e.g. a newly added Anthropic event) ahead of
+    /// crate support, at the cost of doing their own parsing. Only the
+    /// streaming path is supported, since `chat_with_options(stream: false)`'s
+    /// single JSON response isn't SSE to begin with.
+    pub async fn chat_raw(
+        &self,
+        model: &ModelId,
+        context: Context,
+        options: &ChatOptions,
+    ) -> ResultStream<RawSseEvent, anyhow::Error> {
+        let request = self.build_request(model, context, options)?;
+        let body = serialize_with_size_guard(
+            &request,
+            self.max_request_bytes,
+            options.extra_body.as_ref(),
+        )?;
+
+        let url = self.url("/messages")?;
+        debug!(url = %url, model = %model, "Connecting Upstream (raw)");
+        let es = self
+            .client
+            .post(url.clone())
+            .headers(self.headers())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .eventsource()
+            .with_context(|| format_http_context(None, "POST", &url))?;
+
+        let stream = with_token_timeouts(
+            Box::pin(es.take_while(|message| {
+                !matches!(message, Err(reqwest_eventsource::Error::StreamEnded))
+            })),
+            self.first_token_timeout,
+            self.inter_token_timeout,
+        )
+        .map(|event| match event {
+            Err(timeout) => Some(Err(timeout.into())),
+            Ok(Ok(Event::Open)) => None,
+            Ok(Ok(Event::Message(event))) if ["[DONE]", ""].contains(&event.data.trim()) => {
+                debug!("Received completion from Upstream");
+                None
+            }
+            Ok(Ok(Event::Message(message))) => Some(
+                serde_json::from_str::<serde_json::Value>(&message.data)
+                    .with_context(|| "Failed to parse Anthropic event as JSON")
+                    .map(|data| RawSseEvent { event: message.event, data }),
+            ),
+            Ok(Err(reqwest_eventsource::Error::StreamEnded)) => None,
+            Ok(Err(error)) => {
+                tracing::error!(error = ?error, "Failed to receive raw chat event");
+                Some(Err(error.into()))
+            }
+        })
+        .map(move |response| match response {
+            Some(Err(err)) => {
+                Some(Err(err).with_context(|| format_http_context(None, "POST", &url)))
+            }
+            _ => response,
+        });
+
+        Ok(Box::pin(stream.filter_map(|x| x)))
+    }
+
+    /// Like `chat()`, but surfaces heartbeat frames (Anthropic's `ping`
+    /// event, sent to hold the connection open during a long tool execution
+    /// upstream) as [`StreamEvent::KeepAlive`] instead of dropping them
+    /// silently, for callers that want to show a "thinking..." indicator
+    /// while waiting. Real content still arrives as
+    /// [`StreamEvent::Content`], normalized exactly as `chat()` would.
+    pub async fn chat_with_keepalive(
+        &self,
+        model: &ModelId,
+        context: Context,
+        options: &ChatOptions,
+    ) -> ResultStream<StreamEvent, anyhow::Error> {
+        let request = self.build_request(model, context, options)?;
+        let body = serialize_with_size_guard(
+            &request,
+            self.max_request_bytes,
+            options.extra_body.as_ref(),
+        )?;
+
+        let url = self.url("/messages")?;
+        debug!(url = %url, model = %model, "Connecting Upstream (keepalive)");
+        let es = self
+            .client
+            .post(url.clone())
+            .headers(self.headers())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .eventsource()
+            .with_context(|| format_http_context(None, "POST", &url))?;
+
+        let stream = with_token_timeouts(
+            Box::pin(es.take_while(|message| {
+                !matches!(message, Err(reqwest_eventsource::Error::StreamEnded))
+            })),
+            self.first_token_timeout,
+            self.inter_token_timeout,
+        )
+        .map(|event| match event {
+            Err(timeout) => Some(Err(timeout.into())),
+            Ok(Ok(Event::Open)) => None,
+            Ok(Ok(Event::Message(event))) if ["[DONE]", ""].contains(&event.data.trim()) => {
+                debug!("Received completion from Upstream");
+                None
+            }
+            Ok(Ok(Event::Message(message))) => {
+                match serde_json::from_str::<EventData>(&message.data)
+                    .with_context(|| "Failed to parse Anthropic event")
+                {
+                    Ok(EventData::KnownEvent(super::response::Event::Ping)) => {
+                        debug!("Received keepalive ping from Upstream");
+                        Some(Ok(StreamEvent::KeepAlive))
+                    }
+                    Ok(event) => Some(
+                        ChatCompletionMessage::try_from(event)
+                            .with_context(|| {
+                                format!("Failed to create completion message: {}", message.data)
+                            })
+                            .map(StreamEvent::Content),
+                    ),
+                    Err(err) => Some(Err(err)),
+                }
+            }
+            Ok(Err(reqwest_eventsource::Error::StreamEnded)) => None,
+            Ok(Err(error)) => {
+                tracing::error!(error = ?error, "Failed to receive keepalive chat event");
+                Some(Err(error.into()))
+            }
+        })
+        .map(move |response| match response {
+            Some(Err(err)) => {
+                Some(Err(err).with_context(|| format_http_context(None, "POST", &url)))
+            }
+            _ => response,
+        });
+
+        Ok(Box::pin(stream.filter_map(|x| x)))
+    }
+
+    async fn inner_chat(
+        &self,
+        model: &ModelId,
+        context: Context,
+        options: &ChatOptions,
+    ) -> ResultStream<ChatCompletionMessage, anyhow::Error> {
+        let request = self.build_request(model, context, options)?;
+        let body = serialize_with_size_guard(
+            &request,
+            self.max_request_bytes,
+            options.extra_body.as_ref(),
+        )?;
 
         let url = self.url("/messages")?;
         debug!(url = %url, model = %model, "Connecting Upstream");
@@ -81,67 +448,88 @@ impl Anthropic {
             .client
             .post(url.clone())
             .headers(self.headers())
-            .json(&request)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
             .eventsource()
             .with_context(|| format_http_context(None, "POST", &url))?;
 
-        let stream = es
-            .take_while(|message| !matches!(message, Err(reqwest_eventsource::Error::StreamEnded)))
-            .then(|event| async {
-                match event {
-                    Ok(event) => match event {
-                        Event::Open => None,
-                        Event::Message(event) if ["[DONE]", ""].contains(&event.data.as_str()) => {
-                            debug!("Received completion from Upstream");
-                            None
-                        }
-                        Event::Message(message) => Some(
-                            serde_json::from_str::<EventData>(&message.data)
-                                .with_context(|| "Failed to parse Anthropic event")
-                                .and_then(|event| {
-                                    ChatCompletionMessage::try_from(event).with_context(|| {
-                                        format!(
-                                            "Failed to create completion message: {}",
-                                            message.data
-                                        )
-                                    })
-                                }),
-                        ),
-                    },
-                    Err(error) => match error {
-                        reqwest_eventsource::Error::StreamEnded => None,
-                        reqwest_eventsource::Error::InvalidStatusCode(_, response) => {
-                            let status = response.status();
-                            let body = response.text().await.ok();
-                            Some(Err(Error::InvalidStatusCode(status.as_u16())).with_context(
-                                || match body {
-                                    Some(body) => {
-                                        format!("Invalid status code: {status} Reason: {body}")
-                                    }
-                                    None => {
-                                        format!("Invalid status code: {status} Reason: [Unknown]")
-                                    }
-                                },
-                            ))
-                        }
-                        reqwest_eventsource::Error::InvalidContentType(_, ref response) => {
-                            let status_code = response.status();
-                            debug!(response = ?response, "Invalid content type");
-                            Some(Err(error).with_context(|| format!("Http Status: {status_code}")))
+        let stream = with_token_timeouts(
+            Box::pin(es.take_while(|message| {
+                !matches!(message, Err(reqwest_eventsource::Error::StreamEnded))
+            })),
+            self.first_token_timeout,
+            self.inter_token_timeout,
+        )
+        .then(|event| async {
+            match event {
+                Err(timeout) => Some(Err(timeout.into())),
+                Ok(Ok(event)) => match event {
+                    Event::Open => None,
+                    // See the matching comment in `ForgeProvider::inner_chat`:
+                    // `event.data` is trimmed since spec-correct multi-line
+                    // `data:` concatenation can leave stray whitespace around
+                    // an otherwise-terminal event.
+                    Event::Message(event) if ["[DONE]", ""].contains(&event.data.trim()) => {
+                        debug!("Received completion from Upstream");
+                        None
+                    }
+                    Event::Message(message) => {
+                        match serde_json::from_str::<EventData>(&message.data)
+                            .with_context(|| "Failed to parse Anthropic event")
+                        {
+                            // A heartbeat frame, sent to hold the connection open during a
+                            // long tool execution upstream - not content, so it's dropped
+                            // rather than forwarded as an empty completion message.
+                            Ok(EventData::KnownEvent(super::response::Event::Ping)) => {
+                                debug!("Received keepalive ping from Upstream");
+                                None
+                            }
+                            Ok(event) => Some(ChatCompletionMessage::try_from(event).with_context(
+                                || format!("Failed to create completion message: {}", message.data),
+                            )),
+                            Err(err) => Some(Err(err)),
                         }
-                        error => {
-                            tracing::error!(error = ?error, "Failed to receive chat completion event");
-                            Some(Err(error.into()))
-                        }
-                    },
-                }
-            })
-            .map(move |response| match response {
-                Some(Err(err)) => {
-                    Some(Err(err).with_context(|| format_http_context(None, "POST", &url)))
-                }
-                _ => response,
-            });
+                    }
+                },
+                Ok(Err(error)) => match error {
+                    reqwest_eventsource::Error::StreamEnded => None,
+                    reqwest_eventsource::Error::InvalidStatusCode(_, response) => {
+                        let status = response.status();
+                        let request_id = extract_request_id(response.headers());
+                        let body = response.text().await.ok();
+                        let result = Err(Error::InvalidStatusCode(status.as_u16())).with_context(
+                            || match body {
+                                Some(body) => {
+                                    format!(
+                                        "Invalid status code: {status} Reason: {}",
+                                        crate::error::describe_error_body(&body)
+                                    )
+                                }
+                                None => {
+                                    format!("Invalid status code: {status} Reason: [Unknown]")
+                                }
+                            },
+                        );
+                        Some(with_request_id_context(result, request_id.as_deref()))
+                    }
+                    reqwest_eventsource::Error::InvalidContentType(_, ref response) => {
+                        let status_code = response.status();
+                        debug!(response = ?response, "Invalid content type");
+                        Some(Err(error).with_context(|| format!("Http Status: {status_code}")))
+                    }
+                    error => {
+                        tracing::error!(error = ?error, "Failed to receive chat completion event");
+                        Some(Err(error.into()))
+                    }
+                },
+            }
+        })
+        .map(move |response| match response {
+            Some(Err(err)) => {
+                Some(Err(err).with_context(|| format_http_context(None, "POST", &url)))
+            }
+            _ => response,
+        });
 
         Ok(Box::pin(stream.filter_map(|x| x)))
     }
@@ -167,6 +555,7 @@ impl Anthropic {
             }
             Ok(response) => {
                 let status = response.status();
+                let request_id = extract_request_id(response.headers());
                 let ctx_msg = format_http_context(Some(response.status()), "GET", &url);
                 let text = response
                     .text()
@@ -174,20 +563,29 @@ impl Anthropic {
                     .with_context(|| ctx_msg.clone())
                     .with_context(|| "Failed to decode response into text")?;
 
-                if status.is_success() {
+                let outcome = if status.is_success() {
                     let response: ListModelResponse = serde_json::from_str(&text)
                         .with_context(|| ctx_msg)
                         .with_context(|| "Failed to deserialize models response")?;
                     Ok(response.data.into_iter().map(Into::into).collect())
                 } else {
                     // treat non 200 response as error.
-                    Err(anyhow::anyhow!(text))
+                    Err(anyhow::anyhow!(crate::error::describe_error_body(&text)))
                         .with_context(|| ctx_msg)
                         .with_context(|| "Failed to fetch the models")
-                }
+                };
+                with_request_id_context(outcome, request_id.as_deref())
             }
         }
     }
+
+    pub async fn embeddings(
+        &self,
+        _model: &ModelId,
+        _inputs: Vec<String>,
+    ) -> anyhow::Result<Vec<Vec<f32>>> {
+        anyhow::bail!("Anthropic does not currently support an embeddings endpoint")
+    }
 }
 
 #[cfg(test)]
@@ -198,6 +596,7 @@ mod tests {
     };
 
     use super::*;
+    use crate::error::ProviderError;
     use crate::mock_server::{normalize_ports, MockServer};
 
     fn create_anthropic(base_url: &str) -> anyhow::Result<Anthropic> {
@@ -206,6 +605,8 @@ mod tests {
             .base_url(Url::parse(base_url)?)
             .anthropic_version("2023-06-01".to_string())
             .api_key("sk-test-key".to_string())
+            .first_token_timeout(Duration::from_secs(30))
+            .inter_token_timeout(Duration::from_secs(300))
             .build()
             .unwrap())
     }
@@ -254,6 +655,8 @@ mod tests {
             .base_url(Url::parse("https://api.anthropic.com/v1/").unwrap())
             .anthropic_version("v1".to_string())
             .api_key("sk-some-key".to_string())
+            .first_token_timeout(Duration::from_secs(30))
+            .inter_token_timeout(Duration::from_secs(300))
             .build()
             .unwrap();
         assert_eq!(
@@ -262,6 +665,43 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_url_does_not_drop_the_last_segment_of_a_base_without_a_trailing_slash() {
+        let anthropic = create_anthropic("https://api.anthropic.com/v1").unwrap();
+        assert_eq!(
+            anthropic.url("messages").unwrap().as_str(),
+            "https://api.anthropic.com/v1/messages"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_url_preserves_a_gateway_custom_path_prefix() {
+        let with_slash = create_anthropic("https://gateway.example.com/api/anthropic/v1/").unwrap();
+        let without_slash = create_anthropic("https://gateway.example.com/api/anthropic/v1").unwrap();
+
+        assert_eq!(
+            with_slash.url("messages").unwrap().as_str(),
+            "https://gateway.example.com/api/anthropic/v1/messages"
+        );
+        assert_eq!(
+            without_slash.url("messages").unwrap().as_str(),
+            "https://gateway.example.com/api/anthropic/v1/messages"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build_chat_request_reflects_a_custom_gateway_path_prefix() {
+        let anthropic = create_anthropic("https://gateway.example.com/api/anthropic/v1").unwrap();
+        let request = anthropic
+            .build_chat_request(&ModelId::new("claude-3-5-sonnet"), Context::default())
+            .unwrap();
+
+        assert_eq!(
+            request["url"],
+            "https://gateway.example.com/api/anthropic/v1/messages"
+        );
+    }
+
     #[tokio::test]
     async fn test_request_conversion() {
         let model_id = ModelId::new("gpt-4");
@@ -296,6 +736,24 @@ mod tests {
         insta::assert_snapshot!(serde_json::to_string_pretty(&request).unwrap());
     }
 
+    #[tokio::test]
+    async fn test_request_conversion_caches_system_prompt_and_last_user_message() {
+        let context = Context::default()
+            .add_message(ContextMessage::system(
+                "You're expert at math, so you should resolve all user queries.",
+            ))
+            .add_message(ContextMessage::user("what's 2 + 2 ?", None));
+        let request = SetCache
+            .transform(Request::try_from(context).unwrap())
+            .model("sonnet-3.5".to_string())
+            .stream(true)
+            .max_tokens(4000u64);
+
+        let payload = serde_json::to_string_pretty(&request).unwrap();
+        assert_eq!(payload.matches(r#""cache_control""#).count(), 2);
+        assert!(payload.contains(r#""type": "ephemeral""#));
+    }
+
     #[tokio::test]
     async fn test_fetch_models_success() -> anyhow::Result<()> {
         let mut fixture = MockServer::new().await;
@@ -362,4 +820,397 @@ mod tests {
         assert!(actual.is_empty());
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_extra_headers_are_sent_on_requests() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/models")
+            .match_header("anthropic-beta", "prompt-caching-2024-07-31")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(create_empty_response().to_string())
+            .create_async()
+            .await;
+
+        let mut extra_headers = HashMap::new();
+        extra_headers.insert(
+            "anthropic-beta".to_string(),
+            "prompt-caching-2024-07-31".to_string(),
+        );
+
+        let anthropic = Anthropic::builder()
+            .client(Client::new())
+            .base_url(Url::parse(&server.url())?)
+            .anthropic_version("2023-06-01".to_string())
+            .api_key("sk-test-key".to_string())
+            .extra_headers(Some(extra_headers))
+            .first_token_timeout(Duration::from_secs(30))
+            .inter_token_timeout(Duration::from_secs(300))
+            .build()
+            .unwrap();
+
+        anthropic.models().await?;
+
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_extra_header_name_is_rejected_at_construction() {
+        let mut extra_headers = HashMap::new();
+        extra_headers.insert("invalid header".to_string(), "value".to_string());
+
+        let result = Anthropic::builder()
+            .client(Client::new())
+            .base_url(Url::parse("https://api.anthropic.com/v1/").unwrap())
+            .anthropic_version("2023-06-01".to_string())
+            .api_key("sk-test-key".to_string())
+            .extra_headers(Some(extra_headers))
+            .first_token_timeout(Duration::from_secs(30))
+            .inter_token_timeout(Duration::from_secs(300))
+            .build();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_build_request_stream_field_toggles() -> anyhow::Result<()> {
+        let anthropic = create_anthropic("https://api.anthropic.com/v1/")?;
+        let model = ModelId::new("claude-3-5-sonnet-20241022");
+
+        let streaming =
+            anthropic.build_request(&model, Context::default(), &ChatOptions::default())?;
+        let non_streaming = anthropic.build_request(
+            &model,
+            Context::default(),
+            &ChatOptions::default().stream(false),
+        )?;
+
+        let streaming = serde_json::to_value(streaming)?;
+        let non_streaming = serde_json::to_value(non_streaming)?;
+
+        assert_eq!(streaming["stream"], serde_json::json!(true));
+        assert_eq!(non_streaming["stream"], serde_json::json!(false));
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_request_merges_sampling_options() -> anyhow::Result<()> {
+        let anthropic = create_anthropic("https://api.anthropic.com/v1/")?;
+        let model = ModelId::new("claude-3-5-sonnet-20241022");
+
+        let options = ChatOptions::default()
+            .temperature(0.5_f32)
+            .top_p(0.9_f32)
+            .max_tokens(1234u64)
+            .stop(vec!["STOP".to_string()]);
+        let request = anthropic.build_request(&model, Context::default(), &options)?;
+        let request = serde_json::to_value(request)?;
+
+        assert_eq!(request["temperature"], serde_json::json!(0.5));
+        assert_eq!(request["top_p"], serde_json::json!(0.9));
+        assert_eq!(request["max_tokens"], serde_json::json!(1234));
+        assert_eq!(request["stop_sequences"], serde_json::json!(["STOP"]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_request_treats_an_empty_stop_vec_as_no_stops() -> anyhow::Result<()> {
+        let anthropic = create_anthropic("https://api.anthropic.com/v1/")?;
+        let model = ModelId::new("claude-3-5-sonnet-20241022");
+
+        let options = ChatOptions::default().stop(Vec::<String>::new());
+        let request = anthropic.build_request(&model, Context::default(), &options)?;
+        let request = serde_json::to_value(request)?;
+
+        assert_eq!(request.get("stop_sequences"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_request_rejects_stop_sequences_over_the_sanity_ceiling() -> anyhow::Result<()> {
+        let anthropic = create_anthropic("https://api.anthropic.com/v1/")?;
+        let model = ModelId::new("claude-3-5-sonnet-20241022");
+
+        let options =
+            ChatOptions::default().stop((0..101).map(|i| i.to_string()).collect::<Vec<_>>());
+        let err = anthropic
+            .build_request(&model, Context::default(), &options)
+            .unwrap_err();
+
+        assert!(err.to_string().contains("Too many stop sequences"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_request_maps_user_to_metadata_user_id() -> anyhow::Result<()> {
+        let anthropic = create_anthropic("https://api.anthropic.com/v1/")?;
+        let model = ModelId::new("claude-3-5-sonnet-20241022");
+
+        let options = ChatOptions::default().user("user-123");
+        let request = anthropic.build_request(&model, Context::default(), &options)?;
+        let request = serde_json::to_value(request)?;
+
+        assert_eq!(request["metadata"]["user_id"], serde_json::json!("user-123"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_request_json_object_forces_structured_output_tool() -> anyhow::Result<()> {
+        let anthropic = create_anthropic("https://api.anthropic.com/v1/")?;
+        let model = ModelId::new("claude-3-5-sonnet-20241022");
+
+        let options = ChatOptions::default().response_format(ChatResponseFormat::JsonObject);
+        let request = anthropic.build_request(&model, Context::default(), &options)?;
+        let request = serde_json::to_value(request)?;
+
+        assert_eq!(request["tools"][0]["name"], serde_json::json!("structured_output"));
+        assert_eq!(
+            request["tools"][0]["input_schema"],
+            serde_json::json!({ "type": "object" })
+        );
+        assert_eq!(
+            request["tool_choice"],
+            serde_json::json!({ "type": "tool", "name": "structured_output" })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_request_json_schema_forces_structured_output_tool() -> anyhow::Result<()> {
+        let anthropic = create_anthropic("https://api.anthropic.com/v1/")?;
+        let model = ModelId::new("claude-3-5-sonnet-20241022");
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": { "answer": { "type": "string" } },
+            "required": ["answer"],
+        });
+
+        let options =
+            ChatOptions::default().response_format(ChatResponseFormat::JsonSchema(schema.clone()));
+        let request = anthropic.build_request(&model, Context::default(), &options)?;
+        let request = serde_json::to_value(request)?;
+
+        assert_eq!(request["tools"][0]["input_schema"], schema);
+        assert_eq!(request["tool_choice"]["name"], serde_json::json!("structured_output"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_request_text_response_format_adds_no_tools() -> anyhow::Result<()> {
+        let anthropic = create_anthropic("https://api.anthropic.com/v1/")?;
+        let model = ModelId::new("claude-3-5-sonnet-20241022");
+
+        let options = ChatOptions::default().response_format(ChatResponseFormat::Text);
+        let request = anthropic.build_request(&model, Context::default(), &options)?;
+        let request = serde_json::to_value(request)?;
+
+        assert!(request.get("tools").is_none());
+        assert!(request.get("tool_choice").is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chat_with_options_non_streaming_parses_single_response() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/messages")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "id": "msg_1",
+                    "type": "message",
+                    "role": "assistant",
+                    "model": "claude-3-5-sonnet-20241022",
+                    "content": [{ "type": "text", "text": "Hello there" }],
+                    "stop_reason": "end_turn",
+                    "stop_sequence": null,
+                    "usage": { "input_tokens": 5, "output_tokens": 2 }
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let anthropic = create_anthropic(&server.url())?;
+        let mut stream = anthropic
+            .chat_with_options(
+                &ModelId::new("claude-3-5-sonnet-20241022"),
+                Context::default(),
+                ChatOptions::default().stream(false),
+            )
+            .await?;
+
+        let first = stream.next().await.unwrap()?;
+        assert_eq!(first.content.as_ref().map(|c| c.as_str()), Some("Hello there"));
+        assert!(stream.next().await.is_none());
+
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chat_non_streaming_captures_request_id_header() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/messages")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_header("request-id", "req_xyz789")
+            .with_body(
+                serde_json::json!({
+                    "id": "msg_1",
+                    "type": "message",
+                    "role": "assistant",
+                    "model": "claude-3-5-sonnet-20241022",
+                    "content": [{ "type": "text", "text": "Hello there" }],
+                    "stop_reason": "end_turn",
+                    "stop_sequence": null,
+                    "usage": { "input_tokens": 5, "output_tokens": 2 }
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let anthropic = create_anthropic(&server.url())?;
+        let mut stream = anthropic
+            .chat_with_options(
+                &ModelId::new("claude-3-5-sonnet-20241022"),
+                Context::default(),
+                ChatOptions::default().stream(false),
+            )
+            .await?;
+
+        let first = stream.next().await.unwrap()?;
+        assert_eq!(first.request_id, Some("req_xyz789".to_string()));
+
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chat_rejects_oversized_request_without_sending() -> anyhow::Result<()> {
+        let server = mockito::Server::new_async().await;
+        let anthropic = Anthropic::builder()
+            .client(Client::new())
+            .base_url(Url::parse(&server.url())?)
+            .anthropic_version("2023-06-01".to_string())
+            .api_key("sk-test-key".to_string())
+            .first_token_timeout(Duration::from_secs(30))
+            .inter_token_timeout(Duration::from_secs(300))
+            .max_request_bytes(Some(64))
+            .build()?;
+
+        let context =
+            Context::default().add_message(ContextMessage::user("a".repeat(256), None));
+
+        let result = anthropic
+            .chat_with_options(
+                &ModelId::new("claude-3-5-sonnet-20241022"),
+                context,
+                ChatOptions::default().stream(false),
+            )
+            .await;
+
+        let error = result.err().expect("expected oversized request to be rejected");
+        assert!(matches!(
+            error.downcast_ref::<ProviderError>(),
+            Some(ProviderError::RequestTooLarge { .. })
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chat_allows_request_under_limit() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/messages")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "id": "msg_1",
+                    "type": "message",
+                    "role": "assistant",
+                    "model": "claude-3-5-sonnet-20241022",
+                    "content": [{ "type": "text", "text": "Hello there" }],
+                    "stop_reason": "end_turn",
+                    "stop_sequence": null,
+                    "usage": { "input_tokens": 5, "output_tokens": 2 }
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let anthropic = Anthropic::builder()
+            .client(Client::new())
+            .base_url(Url::parse(&server.url())?)
+            .anthropic_version("2023-06-01".to_string())
+            .api_key("sk-test-key".to_string())
+            .first_token_timeout(Duration::from_secs(30))
+            .inter_token_timeout(Duration::from_secs(300))
+            .max_request_bytes(Some(1024 * 1024))
+            .build()?;
+
+        let mut stream = anthropic
+            .chat_with_options(
+                &ModelId::new("claude-3-5-sonnet-20241022"),
+                Context::default(),
+                ChatOptions::default().stream(false),
+            )
+            .await?;
+
+        let first = stream.next().await.unwrap()?;
+        assert_eq!(first.content.as_ref().map(|c| c.as_str()), Some("Hello there"));
+
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chat_raw_yields_unnormalized_sse_events() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let body = concat!(
+            "event: message_start\n",
+            r#"data: {"type":"message_start","message":{"id":"msg_1","type":"message","role":"assistant","content":[],"model":"claude-3-5-sonnet-20241022","stop_reason":null,"stop_sequence":null,"usage":{"input_tokens":5,"output_tokens":0}}}"#,
+            "\n\n",
+            "event: content_block_delta\n",
+            r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}}"#,
+            "\n\n",
+        );
+        let mock = server
+            .mock("POST", "/messages")
+            .with_status(200)
+            .with_header("content-type", "text/event-stream")
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let anthropic = create_anthropic(&server.url())?;
+
+        let mut stream = anthropic
+            .chat_raw(
+                &ModelId::new("claude-3-5-sonnet-20241022"),
+                Context::default(),
+                &ChatOptions::default(),
+            )
+            .await?;
+
+        let first = stream.next().await.unwrap()?;
+        assert_eq!(first.event, "message_start");
+        assert_eq!(first.data["type"], "message_start");
+
+        let second = stream.next().await.unwrap()?;
+        assert_eq!(second.event, "content_block_delta");
+        assert_eq!(second.data["delta"]["text"], "Hi");
+
+        assert!(stream.next().await.is_none());
+
+        mock.assert_async().await;
+        Ok(())
+    }
 }
@@ -8,16 +8,16 @@ use crate::error::Error;
 #[setters(into, strip_option)]
 pub struct Request {
     max_tokens: u64,
-    messages: Vec<Message>,
+    pub(crate) messages: Vec<Message>,
     model: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     metadata: Option<Metadata>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    stop_sequence: Option<String>,
+    stop_sequences: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     stream: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    system: Option<String>,
+    pub(crate) system: Option<System>,
     #[serde(skip_serializing_if = "Option::is_none")]
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -75,7 +75,7 @@ impl TryFrom<forge_app::domain::Context> for Request {
                 .into_iter()
                 .map(ToolDefinition::try_from)
                 .collect::<std::result::Result<Vec<_>, _>>()?,
-            system,
+            system: system.map(System::Text),
             temperature: request.temperature.map(|t| t.value()),
             top_p: request.top_p.map(|t| t.value()),
             top_k: request.top_k.map(|t| t.value() as u64),
@@ -94,16 +94,75 @@ impl TryFrom<forge_app::domain::Context> for Request {
     }
 }
 
+impl Request {
+    /// Appends a tool definition without disturbing any already set by
+    /// [`Request::try_from`]. Used by [`super::provider::Anthropic::build_request`]
+    /// to graft on the synthetic [`ToolDefinition::structured_output`] tool
+    /// without dropping the caller's own tools.
+    pub(crate) fn add_tool(mut self, tool: ToolDefinition) -> Self {
+        self.tools.push(tool);
+        self
+    }
+}
+
+/// Anthropic's `system` field accepts either a plain string or an array of
+/// text blocks; the array form is only needed to attach a `cache_control`
+/// marker to the system prompt, so requests that don't cache it keep the
+/// simpler string shape.
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum System {
+    Text(String),
+    Blocks(Vec<SystemBlock>),
+}
+
+impl System {
+    /// Promotes a plain-string system prompt to the block form with a
+    /// `cache_control` marker on it. A no-op if it's already block form.
+    pub(crate) fn mark_cached(&mut self) {
+        if let System::Text(text) = self {
+            let text = std::mem::take(text);
+            *self = System::Blocks(vec![SystemBlock {
+                type_: "text".to_string(),
+                text,
+                cache_control: Some(CacheControl::Ephemeral),
+            }]);
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn is_cached(&self) -> bool {
+        matches!(self, System::Blocks(blocks) if blocks.iter().any(|b| b.cache_control.is_some()))
+    }
+}
+
+#[derive(Serialize)]
+pub struct SystemBlock {
+    #[serde(rename = "type")]
+    type_: String,
+    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache_control: Option<CacheControl>,
+}
+
 #[derive(Serialize)]
 pub struct Metadata {
     #[serde(skip_serializing_if = "Option::is_none")]
     user_id: Option<String>,
 }
 
+impl Metadata {
+    /// Anthropic's `metadata` object only has a documented `user_id` field
+    /// - see [`forge_app::domain::ChatOptions::user`].
+    pub(crate) fn with_user_id(user_id: impl Into<String>) -> Self {
+        Self { user_id: Some(user_id.into()) }
+    }
+}
+
 #[derive(Serialize)]
 pub struct Message {
-    content: Vec<Content>,
-    role: Role,
+    pub(crate) content: Vec<Content>,
+    pub(crate) role: Role,
 }
 
 impl TryFrom<ContextMessage> for Message {
@@ -166,14 +225,26 @@ impl TryFrom<ContextMessage> for Message {
 
 impl From<Image> for Content {
     fn from(value: Image) -> Self {
-        Content::Image {
-            source: ImageSource {
+        // Every `Image` in this codebase is built via `new_bytes`/`new_base64`,
+        // which embed the payload in a `data:` URI rather than pointing at a
+        // real URL, so Anthropic's `base64` source type is the one that
+        // actually applies here; `url` is kept as a fallback in case a future
+        // caller ever constructs an `Image` from a genuine external URL.
+        let source = match value.base64_data() {
+            Some(data) => ImageSource {
+                type_: "base64".to_string(),
+                media_type: Some(value.mime_type().clone()),
+                data: Some(data.to_string()),
+                url: None,
+            },
+            None => ImageSource {
                 type_: "url".to_string(),
                 media_type: None,
                 data: None,
                 url: Some(value.url().clone()),
             },
-        }
+        };
+        Content::Image { source }
     }
 }
 
@@ -224,6 +295,31 @@ enum Content {
     },
 }
 
+impl Content {
+    /// Attaches a `cache_control` marker, for the variants that support one.
+    /// A no-op for `Image`/`Thinking`, which Anthropic doesn't let you cache.
+    pub(crate) fn mark_cached(&mut self) {
+        match self {
+            Content::Text { cache_control, .. }
+            | Content::ToolUse { cache_control, .. }
+            | Content::ToolResult { cache_control, .. } => {
+                *cache_control = Some(CacheControl::Ephemeral);
+            }
+            Content::Image { .. } | Content::Thinking { .. } => {}
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn is_cached(&self) -> bool {
+        matches!(
+            self,
+            Content::Text { cache_control: Some(_), .. }
+                | Content::ToolUse { cache_control: Some(_), .. }
+                | Content::ToolResult { cache_control: Some(_), .. }
+        )
+    }
+}
+
 impl TryFrom<forge_app::domain::ToolCallFull> for Content {
     type Error = anyhow::Error;
     fn try_from(value: forge_app::domain::ToolCallFull) -> std::result::Result<Self, Self::Error> {
@@ -258,7 +354,6 @@ impl TryFrom<forge_app::domain::ToolResult> for Content {
 
 #[derive(Serialize)]
 #[serde(rename_all = "snake_case")]
-#[allow(dead_code)]
 pub enum CacheControl {
     Ephemeral,
 }
@@ -331,3 +426,47 @@ impl TryFrom<forge_app::domain::ToolDefinition> for ToolDefinition {
         })
     }
 }
+
+/// Name of the synthetic tool [`ToolDefinition::structured_output`] creates.
+/// Anthropic has no native `response_format` parameter, so a
+/// [`ChatOptions::response_format`] of `JsonObject`/`JsonSchema` is instead
+/// enforced by forcing a single tool call to a tool by this name, whose
+/// `input_schema` mirrors the desired JSON shape - the same trick used
+/// before Anthropic shipped native structured outputs.
+pub(crate) const STRUCTURED_OUTPUT_TOOL_NAME: &str = "structured_output";
+
+impl ToolDefinition {
+    /// Builds the synthetic tool described by [`STRUCTURED_OUTPUT_TOOL_NAME`].
+    pub(crate) fn structured_output(schema: serde_json::Value) -> Self {
+        ToolDefinition {
+            name: STRUCTURED_OUTPUT_TOOL_NAME.to_string(),
+            description: Some(
+                "Return the final answer matching the requested JSON shape.".to_string(),
+            ),
+            cache_control: None,
+            input_schema: schema,
+        }
+    }
+}
+
+#[cfg(test)]
+mod image_content_tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_image_serializes_as_base64_source() {
+        let image = Image::new_base64("aGVsbG8=".to_string(), "image/png");
+        let content = Content::from(image);
+
+        let actual = serde_json::to_value(&content).unwrap();
+        let expected = serde_json::json!({
+            "type": "image",
+            "source": {
+                "type": "base64",
+                "media_type": "image/png",
+                "data": "aGVsbG8="
+            }
+        });
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,6 +1,6 @@
 use forge_app::domain::{
-    ChatCompletionMessage, Content, ModelId, Reasoning, ReasoningPart, ToolCallId, ToolCallPart,
-    ToolName,
+    ChatCompletionMessage, Content, ModelId, Reasoning, ReasoningFull, ReasoningPart, ToolCallFull,
+    ToolCallId, ToolCallPart, ToolName,
 };
 use serde::Deserialize;
 
@@ -18,16 +18,25 @@ pub struct Model {
     display_name: String,
 }
 
+/// Anthropic's `/v1/models` endpoint doesn't report context length or
+/// capability flags, but every model it can currently return supports tool
+/// use and vision, and as of Claude 3 shares the same 200k-token context
+/// window, so those are filled in as sensible defaults rather than left
+/// unknown.
+const DEFAULT_CONTEXT_LENGTH: u64 = 200_000;
+
 impl From<Model> for forge_app::domain::Model {
     fn from(value: Model) -> Self {
         Self {
             id: ModelId::new(value.id),
             name: Some(value.display_name),
             description: None,
-            context_length: None,
+            context_length: Some(DEFAULT_CONTEXT_LENGTH),
             tools_supported: Some(true),
             supports_parallel_tool_calls: None,
             supports_reasoning: None,
+            supports_vision: Some(true),
+            deprecated: None,
         }
     }
 }
@@ -44,6 +53,12 @@ pub struct MessageStart {
     pub usage: Usage,
 }
 
+/// Anthropic's non-streaming `/messages` response shares the exact same
+/// top-level shape as the `message_start` SSE event's nested `message`
+/// object, just with `content` already fully populated instead of being
+/// filled in by later `content_block_*` events.
+pub type MessageResponse = MessageStart;
+
 #[derive(Deserialize, PartialEq, Clone, Debug)]
 pub struct Usage {
     pub input_tokens: Option<usize>,
@@ -60,6 +75,7 @@ impl From<Usage> for forge_app::domain::Usage {
             completion_tokens: usage.output_tokens.unwrap_or(0),
             total_tokens: usage.input_tokens.unwrap_or(0) + usage.output_tokens.unwrap_or(0),
             cached_tokens: usage.cache_read_input_tokens.unwrap_or_default(),
+            cache_write_tokens: usage.cache_creation_input_tokens.unwrap_or_default(),
             ..Default::default()
         }
     }
@@ -80,7 +96,7 @@ impl From<StopReason> for forge_app::domain::FinishReason {
             StopReason::EndTurn => forge_app::domain::FinishReason::Stop,
             StopReason::MaxTokens => forge_app::domain::FinishReason::Length,
             StopReason::StopSequence => forge_app::domain::FinishReason::Stop,
-            StopReason::ToolUse => forge_app::domain::FinishReason::ToolCalls,
+            StopReason::ToolUse => forge_app::domain::FinishReason::ToolUse,
         }
     }
 }
@@ -256,6 +272,7 @@ impl TryFrom<ContentBlock> for ChatCompletionMessage {
                     } else {
                         serde_json::to_string(&input)?
                     },
+                    index: None,
                 })
             }
             ContentBlock::InputJsonDelta { partial_json } => {
@@ -263,6 +280,7 @@ impl TryFrom<ContentBlock> for ChatCompletionMessage {
                     call_id: None,
                     name: None,
                     arguments_part: partial_json,
+                    index: None,
                 })
             }
         };
@@ -271,10 +289,84 @@ impl TryFrom<ContentBlock> for ChatCompletionMessage {
     }
 }
 
+impl TryFrom<MessageResponse> for ChatCompletionMessage {
+    type Error = anyhow::Error;
+
+    fn try_from(value: MessageResponse) -> Result<Self, Self::Error> {
+        let mut text = String::new();
+        let mut response = ChatCompletionMessage::assistant(Content::full(""));
+
+        for block in value.content {
+            match block {
+                ContentBlock::Text { text: block_text } => text.push_str(&block_text),
+                ContentBlock::Thinking { thinking, signature } => {
+                    if let Some(thinking) = thinking {
+                        response = response
+                            .reasoning(Content::full(thinking.clone()))
+                            .add_reasoning_detail(Reasoning::Full(vec![ReasoningFull {
+                                signature,
+                                text: Some(thinking),
+                            }]));
+                    }
+                }
+                ContentBlock::RedactedThinking { data } => {
+                    if let Some(data) = data {
+                        response = response
+                            .reasoning(Content::full(data.clone()))
+                            .add_reasoning_detail(Reasoning::Full(vec![ReasoningFull {
+                                signature: None,
+                                text: Some(data),
+                            }]));
+                    }
+                }
+                ContentBlock::ToolUse { id, name, input } => {
+                    response = response.add_tool_call(ToolCallFull {
+                        call_id: Some(ToolCallId::new(id)),
+                        name: ToolName::new(name),
+                        arguments: input,
+                    });
+                }
+                // Delta-only variants never appear in a fully-assembled non-streaming response.
+                ContentBlock::TextDelta { .. }
+                | ContentBlock::InputJsonDelta { .. }
+                | ContentBlock::ThinkingDelta { .. }
+                | ContentBlock::SignatureDelta { .. } => {}
+            }
+        }
+
+        response = response
+            .content(Content::full(text))
+            .usage(forge_app::domain::Usage::from(value.usage));
+        Ok(response.finish_reason_opt(value.stop_reason.map(Into::into)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_stop_reason_maps_to_normalized_finish_reason() {
+        use forge_app::domain::FinishReason;
+
+        assert_eq!(
+            forge_app::domain::FinishReason::from(StopReason::EndTurn),
+            FinishReason::Stop
+        );
+        assert_eq!(
+            forge_app::domain::FinishReason::from(StopReason::MaxTokens),
+            FinishReason::Length
+        );
+        assert_eq!(
+            forge_app::domain::FinishReason::from(StopReason::StopSequence),
+            FinishReason::Stop
+        );
+        assert_eq!(
+            forge_app::domain::FinishReason::from(StopReason::ToolUse),
+            FinishReason::ToolUse
+        );
+    }
+
     #[test]
     fn test_unknow_event() {
         let event = r#"{"type": "random_error", "error": {"type": "overloaded_error", "message": "Overloaded"}}"#;
@@ -370,6 +462,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_content_block_delta_splits_thinking_from_text() {
+        // A thinking model interleaves `thinking_delta` and `text_delta` blocks in
+        // the same stream; each must map to its own side of `ChatCompletionMessage`
+        // rather than being collapsed into a single `content` field.
+        let thinking_delta: Event = serde_json::from_str(
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"thinking_delta","thinking":"Let me work through this..."}}"#,
+        )
+        .unwrap();
+        let text_delta: Event = serde_json::from_str(
+            r#"{"type":"content_block_delta","index":1,"delta":{"type":"text_delta","text":"The answer is 42."}}"#,
+        )
+        .unwrap();
+
+        let thinking_message = ChatCompletionMessage::try_from(thinking_delta).unwrap();
+        assert_eq!(
+            thinking_message.reasoning,
+            Some(Content::part("Let me work through this..."))
+        );
+        assert_eq!(thinking_message.content, Some(Content::part("")));
+
+        let text_message = ChatCompletionMessage::try_from(text_delta).unwrap();
+        assert_eq!(text_message.content, Some(Content::part("The answer is 42.")));
+        assert_eq!(text_message.reasoning, None);
+    }
+
     #[test]
     fn test_model_deser() {
         let input = r#"{
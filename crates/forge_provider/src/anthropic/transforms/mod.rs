@@ -1,3 +1,5 @@
 mod reasoning_transform;
+mod set_cache;
 
 pub use reasoning_transform::ReasoningTransform;
+pub use set_cache::SetCache;
@@ -0,0 +1,93 @@
+use forge_app::domain::Transformer;
+
+use crate::anthropic::request::{Request, Role};
+
+/// Marks the system prompt and the latest user turn as cacheable via
+/// Anthropic's `cache_control: {type: "ephemeral"}` marker, so a repeated
+/// large system prompt (and the conversation prefix leading up to the most
+/// recent user turn) is billed at the cached rate on follow-up requests
+/// instead of being reprocessed in full every time.
+pub struct SetCache;
+
+impl Transformer for SetCache {
+    type Value = Request;
+
+    fn transform(&mut self, mut request: Self::Value) -> Self::Value {
+        if let Some(system) = request.system.as_mut() {
+            system.mark_cached();
+        }
+
+        let last_user_message = request
+            .messages
+            .iter_mut()
+            .rfind(|message| message.role == Role::User);
+        if let Some(message) = last_user_message {
+            if let Some(content) = message.content.last_mut() {
+                content.mark_cached();
+            }
+        }
+
+        request
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_app::domain::{Context, ContextMessage, ToolCallFull, ToolCallId, ToolName};
+
+    use super::*;
+
+    fn create_context_fixture() -> Context {
+        Context::default()
+            .add_message(ContextMessage::system("be helpful"))
+            .add_message(ContextMessage::user("what's 2 + 2 ?", None))
+            .add_message(ContextMessage::assistant(
+                "here is the system call.",
+                None,
+                Some(vec![ToolCallFull {
+                    name: ToolName::new("math"),
+                    call_id: Some(ToolCallId::new("math-1")),
+                    arguments: serde_json::json!({"expression": "2 + 2"}),
+                }]),
+            ))
+    }
+
+    #[test]
+    fn test_caches_system_prompt_and_last_user_message() {
+        let request = Request::try_from(create_context_fixture()).unwrap();
+        let mut transformer = SetCache;
+        let request = transformer.transform(request);
+
+        assert!(request.system.as_ref().unwrap().is_cached());
+        let user_message = request
+            .messages
+            .iter()
+            .find(|message| message.role == Role::User)
+            .unwrap();
+        assert!(user_message.content.last().unwrap().is_cached());
+    }
+
+    #[test]
+    fn test_leaves_non_system_non_user_content_uncached() {
+        let request = Request::try_from(create_context_fixture()).unwrap();
+        let mut transformer = SetCache;
+        let request = transformer.transform(request);
+
+        let assistant_message = request
+            .messages
+            .iter()
+            .find(|message| message.role == Role::Assistant)
+            .unwrap();
+        assert!(assistant_message.content.iter().all(|c| !c.is_cached()));
+    }
+
+    #[test]
+    fn test_is_a_no_op_without_a_system_prompt() {
+        let context = Context::default().add_message(ContextMessage::user("hi", None));
+        let request = Request::try_from(context).unwrap();
+        let mut transformer = SetCache;
+        let request = transformer.transform(request);
+
+        assert!(request.system.is_none());
+    }
+}
@@ -0,0 +1,33 @@
+use forge_app::domain::ModelId;
+
+/// A single completed `chat`/`chat_with_options` call, captured once its
+/// response stream - successful or not - has fully drained, for
+/// [`crate::Client::with_audit_log`]. `request`/`response` have already been
+/// passed through the redactor registered alongside the sink by the time an
+/// [`AuditSink`] sees this.
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    pub provider: &'static str,
+    pub model: ModelId,
+    /// The outgoing [`forge_app::domain::Context`], JSON-serialized so the
+    /// redactor - a plain `Fn(&str) -> String` - can run over it the same way
+    /// it runs over `response`.
+    pub request: String,
+    /// The assembled response text, or `None` if the stream yielded no
+    /// content (e.g. a tool-call-only turn, or a call that failed before
+    /// producing anything).
+    pub response: Option<String>,
+    /// Set instead of - or in addition to - `response` when the stream
+    /// errored partway through.
+    pub error: Option<String>,
+}
+
+/// Receives one [`AuditEntry`] per completed `chat`/`chat_with_options` call,
+/// for [`crate::Client::with_audit_log`]. `record` runs on a task spawned
+/// after the response stream has already been handed back to the caller, so
+/// a slow or panicking sink never adds latency to - or breaks - the request
+/// it's auditing.
+#[async_trait::async_trait]
+pub trait AuditSink: Send + Sync {
+    async fn record(&self, entry: AuditEntry);
+}
@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use anyhow::{Context as _, Result};
+use derive_builder::Builder;
+use forge_app::domain::{ChatCompletionMessage, Context as ChatContext, Model, ModelId, ResultStream};
+use reqwest::{Client, Url};
+use reqwest_eventsource::{Event, RequestBuilderExt};
+use tokio_stream::StreamExt;
+use tracing::debug;
+
+use super::request::Request;
+use super::response::Response;
+use crate::error::Error;
+use crate::utils::format_http_context;
+
+#[derive(Clone, Builder)]
+pub struct AzureOpenAI {
+    client: Client,
+    endpoint: Url,
+    api_key: String,
+    api_version: String,
+    deployment_map: HashMap<ModelId, String>,
+}
+
+impl AzureOpenAI {
+    pub fn builder() -> AzureOpenAIBuilder {
+        AzureOpenAIBuilder::default()
+    }
+
+    fn deployment_for(&self, model: &ModelId) -> Result<&str> {
+        self.deployment_map.get(model).map(String::as_str).ok_or_else(|| {
+            anyhow::anyhow!(
+                "No Azure deployment configured for model `{model}`. Add an entry for it to `deployment_map`."
+            )
+        })
+    }
+
+    fn url(&self, deployment: &str, path: &str) -> Result<Url> {
+        let mut url = self
+            .endpoint
+            .join(&format!("openai/deployments/{deployment}/{path}"))
+            .with_context(|| format!("Failed to build Azure OpenAI URL for deployment `{deployment}`"))?;
+        url.query_pairs_mut().append_pair("api-version", &self.api_version);
+        Ok(url)
+    }
+
+    /// Builds the exact JSON body and headers `chat()` would send for
+    /// `model`/`context`, without performing any I/O. Useful for diagnosing
+    /// why a provider rejects a payload, since it reflects the same
+    /// serialization `chat()` uses. The `api-key` header is redacted.
+    pub fn build_chat_request(
+        &self,
+        model: &ModelId,
+        context: ChatContext,
+    ) -> Result<serde_json::Value> {
+        let deployment = self.deployment_for(model)?;
+        let url = self.url(deployment, "chat/completions")?;
+
+        let mut request = Request::from(context);
+        request.stream = true;
+
+        Ok(serde_json::json!({
+            "url": url.to_string(),
+            "headers": {"api-key": "[REDACTED]"},
+            "body": request,
+        }))
+    }
+
+    pub async fn chat(
+        &self,
+        model: &ModelId,
+        context: ChatContext,
+    ) -> ResultStream<ChatCompletionMessage, anyhow::Error> {
+        let deployment = self.deployment_for(model)?;
+        let url = self.url(deployment, "chat/completions")?;
+
+        let mut request = Request::from(context);
+        request.stream = true;
+
+        debug!(url = %url, model = %model, deployment = %deployment, "Connecting Upstream");
+
+        let es = self
+            .client
+            .post(url.clone())
+            .header("api-key", &self.api_key)
+            .json(&request)
+            .eventsource()
+            .with_context(|| format_http_context(None, "POST", &url))?;
+
+        let stream = es
+            .take_while(|message| !matches!(message, Err(reqwest_eventsource::Error::StreamEnded)))
+            .then(|event| async {
+                match event {
+                    Ok(Event::Open) => None,
+                    Ok(Event::Message(message)) if ["[DONE]", ""].contains(&message.data.as_str()) => {
+                        None
+                    }
+                    Ok(Event::Message(message)) => Some(
+                        serde_json::from_str::<Response>(&message.data)
+                            .with_context(|| format!("Failed to parse Azure OpenAI event: {}", message.data))
+                            .and_then(ChatCompletionMessage::try_from),
+                    ),
+                    Err(reqwest_eventsource::Error::StreamEnded) => None,
+                    Err(reqwest_eventsource::Error::InvalidStatusCode(_, response)) => {
+                        let status = response.status();
+                        let body = response.text().await.ok();
+                        Some(Err(Error::InvalidStatusCode(status.as_u16())).with_context(|| {
+                            match body {
+                                Some(body) => format!("{status} Reason: {body}"),
+                                None => format!("{status} Reason: [Unknown]"),
+                            }
+                        }))
+                    }
+                    Err(error) => {
+                        tracing::error!(error = ?error, "Failed to receive chat completion event");
+                        Some(Err(error.into()))
+                    }
+                }
+            })
+            .map(move |response| match response {
+                Some(Err(err)) => Some(Err(err).with_context(|| format_http_context(None, "POST", &url))),
+                _ => response,
+            });
+
+        Ok(Box::pin(stream.filter_map(|x| x)))
+    }
+
+    /// Azure doesn't expose a generic "list all base models" endpoint scoped
+    /// to an API key the way OpenAI does; what's actually deployed is exactly
+    /// what the caller configured in `deployment_map`, so that's what we
+    /// report back.
+    pub async fn models(&self) -> Result<Vec<Model>> {
+        Ok(self
+            .deployment_map
+            .keys()
+            .cloned()
+            .map(|id| Model {
+                id,
+                name: None,
+                description: None,
+                context_length: None,
+                tools_supported: None,
+                supports_parallel_tool_calls: None,
+                supports_reasoning: None,
+                supports_vision: None,
+                deprecated: None,
+            })
+            .collect())
+    }
+
+    pub async fn embeddings(&self, _model: &ModelId, _inputs: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        anyhow::bail!("Azure OpenAI embeddings are not yet supported by this client")
+    }
+}
@@ -0,0 +1,159 @@
+use forge_app::domain::{
+    Context, ContextMessage, Role, ToolCallFull, ToolDefinition, ToolName, ToolOutput, ToolValue,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Default)]
+pub struct Request {
+    pub messages: Vec<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<Tool>>,
+    pub stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Message {
+    pub role: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<ToolName>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCall>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ToolCall {
+    pub id: Option<String>,
+    pub r#type: String,
+    pub function: FunctionCall,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FunctionCall {
+    pub name: Option<ToolName>,
+    #[serde(default)]
+    pub arguments: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Tool {
+    pub r#type: &'static str,
+    pub function: FunctionDescription,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FunctionDescription {
+    pub name: String,
+    pub description: String,
+    pub parameters: serde_json::Value,
+}
+
+impl From<ToolDefinition> for Tool {
+    fn from(value: ToolDefinition) -> Self {
+        Tool {
+            r#type: "function",
+            function: FunctionDescription {
+                name: value.name.to_string(),
+                description: value.description,
+                parameters: serde_json::to_value(value.input_schema).unwrap(),
+            },
+        }
+    }
+}
+
+impl From<ToolCallFull> for ToolCall {
+    fn from(value: ToolCallFull) -> Self {
+        ToolCall {
+            id: value.call_id.map(|id| id.as_str().to_string()),
+            r#type: "function".to_string(),
+            function: FunctionCall {
+                name: Some(value.name),
+                arguments: serde_json::to_string(&value.arguments).unwrap_or_default(),
+            },
+        }
+    }
+}
+
+impl From<Context> for Request {
+    fn from(context: Context) -> Self {
+        let messages = context
+            .messages
+            .into_iter()
+            .map(Message::from)
+            .collect::<Vec<_>>();
+
+        let tools = context
+            .tools
+            .into_iter()
+            .map(Tool::from)
+            .collect::<Vec<_>>();
+
+        Request {
+            messages,
+            tools: (!tools.is_empty()).then_some(tools),
+            stream: false,
+            max_tokens: context.max_tokens.map(|t| t as u32),
+            temperature: context.temperature.map(|t| t.value()),
+            top_p: context.top_p.map(|t| t.value()),
+        }
+    }
+}
+
+impl From<ContextMessage> for Message {
+    fn from(message: ContextMessage) -> Self {
+        match message {
+            ContextMessage::Text(text) => {
+                let role = match text.role {
+                    Role::System => "system",
+                    Role::User => "user",
+                    Role::Assistant => "assistant",
+                };
+
+                Message {
+                    role: role.to_string(),
+                    content: Some(text.content),
+                    name: None,
+                    tool_call_id: None,
+                    tool_calls: text
+                        .tool_calls
+                        .map(|calls| calls.into_iter().map(ToolCall::from).collect()),
+                }
+            }
+            ContextMessage::Tool(tool_result) => Message {
+                role: "tool".to_string(),
+                content: Some(tool_output_to_text(&tool_result.output)),
+                name: Some(tool_result.name),
+                tool_call_id: tool_result.call_id.map(|id| id.as_str().to_string()),
+                tool_calls: None,
+            },
+            ContextMessage::Image(_) => Message {
+                role: "user".to_string(),
+                content: Some(String::new()),
+                name: None,
+                tool_call_id: None,
+                tool_calls: None,
+            },
+        }
+    }
+}
+
+fn tool_output_to_text(output: &ToolOutput) -> String {
+    output
+        .values
+        .iter()
+        .filter_map(|value| match value {
+            ToolValue::Text(text) => Some(text.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
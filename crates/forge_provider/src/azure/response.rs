@@ -0,0 +1,161 @@
+use forge_app::domain::{
+    ChatCompletionMessage, Content, FinishReason, ToolCallFull, ToolCallId, ToolCallPart, ToolName,
+    Usage,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(untagged)]
+pub enum Response {
+    Success { choices: Vec<Choice>, usage: Option<ResponseUsage> },
+    Failure { error: ErrorResponse },
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ErrorResponse {
+    pub message: String,
+    pub code: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ResponseUsage {
+    pub prompt_tokens: usize,
+    pub completion_tokens: usize,
+    pub total_tokens: usize,
+}
+
+impl From<ResponseUsage> for Usage {
+    fn from(usage: ResponseUsage) -> Self {
+        Usage {
+            prompt_tokens: usage.prompt_tokens,
+            completion_tokens: usage.completion_tokens,
+            total_tokens: usage.total_tokens,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(untagged)]
+pub enum Choice {
+    NonStreaming { finish_reason: Option<String>, message: ResponseMessage },
+    Streaming { finish_reason: Option<String>, delta: ResponseMessage },
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ResponseMessage {
+    pub content: Option<String>,
+    pub tool_calls: Option<Vec<ToolCall>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ToolCall {
+    pub id: Option<String>,
+    pub function: FunctionCall,
+    /// Present on streaming deltas; identifies which tool call a fragment
+    /// belongs to when multiple tool calls stream interleaved with one
+    /// another.
+    #[serde(default)]
+    pub index: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct FunctionCall {
+    pub name: Option<ToolName>,
+    #[serde(default)]
+    pub arguments: String,
+}
+
+impl TryFrom<Response> for ChatCompletionMessage {
+    type Error = anyhow::Error;
+
+    fn try_from(response: Response) -> Result<Self, Self::Error> {
+        match response {
+            Response::Success { choices, usage } => {
+                let Some(choice) = choices.into_iter().next() else {
+                    return Ok(ChatCompletionMessage::assistant(Content::full("")));
+                };
+
+                let mut message = match choice {
+                    Choice::NonStreaming { finish_reason, message } => {
+                        let mut resp = ChatCompletionMessage::assistant(Content::full(
+                            message.content.unwrap_or_default(),
+                        ))
+                        .finish_reason_opt(finish_reason.as_deref().and_then(map_finish_reason));
+
+                        for tool_call in message.tool_calls.into_iter().flatten() {
+                            resp = resp.add_tool_call(ToolCallFull {
+                                call_id: tool_call.id.map(ToolCallId::new),
+                                name: tool_call
+                                    .function
+                                    .name
+                                    .ok_or_else(|| anyhow::anyhow!("Tool call is missing a name"))?,
+                                arguments: serde_json::from_str(&tool_call.function.arguments)?,
+                            });
+                        }
+                        resp
+                    }
+                    Choice::Streaming { finish_reason, delta } => {
+                        let mut resp = ChatCompletionMessage::assistant(Content::part(
+                            delta.content.unwrap_or_default(),
+                        ))
+                        .finish_reason_opt(finish_reason.as_deref().and_then(map_finish_reason));
+
+                        for tool_call in delta.tool_calls.into_iter().flatten() {
+                            resp = resp.add_tool_call(ToolCallPart {
+                                call_id: tool_call.id.map(ToolCallId::new),
+                                name: tool_call.function.name,
+                                arguments_part: tool_call.function.arguments,
+                                index: tool_call.index,
+                            });
+                        }
+                        resp
+                    }
+                };
+
+                if let Some(usage) = usage {
+                    message.usage = Some(usage.into());
+                }
+                Ok(message)
+            }
+            Response::Failure { error } => Err(anyhow::anyhow!(error.message)),
+        }
+    }
+}
+
+fn map_finish_reason(reason: &str) -> Option<FinishReason> {
+    match reason {
+        "stop" => Some(FinishReason::Stop),
+        "length" => Some(FinishReason::Length),
+        "tool_calls" | "function_call" => Some(FinishReason::ToolUse),
+        "content_filter" => Some(FinishReason::ContentFilter),
+        other => Some(FinishReason::Other(other.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_map_finish_reason_normalizes_known_values() {
+        assert_eq!(map_finish_reason("stop"), Some(FinishReason::Stop));
+        assert_eq!(map_finish_reason("length"), Some(FinishReason::Length));
+        assert_eq!(map_finish_reason("tool_calls"), Some(FinishReason::ToolUse));
+        assert_eq!(map_finish_reason("function_call"), Some(FinishReason::ToolUse));
+        assert_eq!(
+            map_finish_reason("content_filter"),
+            Some(FinishReason::ContentFilter)
+        );
+    }
+
+    #[test]
+    fn test_map_finish_reason_falls_back_to_other_for_unrecognized_values() {
+        assert_eq!(
+            map_finish_reason("something_new"),
+            Some(FinishReason::Other("something_new".to_string()))
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use anyhow::Context as _;
+use aws_credential_types::Credentials;
+use aws_sigv4::http_request::{sign, SignableBody, SignableRequest, SigningSettings};
+use aws_sigv4::sign::v4;
+use bytes::Bytes;
+use derive_builder::Builder;
+use forge_app::domain::{ChatCompletionMessage, Context, Model, ModelId, ResultStream};
+use futures::Stream;
+use reqwest::{Client, Url};
+use tokio_stream::StreamExt;
+use tracing::debug;
+
+use super::request::build_body;
+use super::response::EventStreamDecoder;
+use crate::utils::format_http_context;
+
+#[derive(Clone, Builder)]
+pub struct Bedrock {
+    client: Client,
+    region: String,
+    /// Overrides the `https://bedrock-runtime.{region}.amazonaws.com`
+    /// endpoint derived from `region`; unset in production, set by tests to
+    /// point at a local mock server while still signing for a real region.
+    base_url: Option<Url>,
+    model_map: HashMap<ModelId, String>,
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+}
+
+impl Bedrock {
+    pub fn builder() -> BedrockBuilder {
+        BedrockBuilder::default()
+    }
+
+    fn endpoint(&self) -> String {
+        match &self.base_url {
+            Some(url) => url.as_str().trim_end_matches('/').to_string(),
+            None => format!("https://bedrock-runtime.{}.amazonaws.com", self.region),
+        }
+    }
+
+    /// Maps a model ID from the caller's vocabulary to the Bedrock model ID
+    /// (e.g. `anthropic.claude-3-5-sonnet-20241022-v2:0`) configured for it.
+    fn bedrock_model_id(&self, model: &ModelId) -> anyhow::Result<&str> {
+        self.model_map
+            .get(model)
+            .map(String::as_str)
+            .with_context(|| format!("No Bedrock model configured for {model}"))
+    }
+
+    /// SigV4-signs `request` for the `bedrock` service, attaching the
+    /// resulting `authorization`/`x-amz-date`/`x-amz-security-token`
+    /// headers in place.
+    fn sign(&self, request: &mut reqwest::Request) -> anyhow::Result<()> {
+        let identity = Credentials::new(
+            &self.access_key_id,
+            &self.secret_access_key,
+            self.session_token.clone(),
+            None,
+            "forge-bedrock",
+        )
+        .into();
+
+        let signing_params = v4::SigningParams::builder()
+            .identity(&identity)
+            .region(&self.region)
+            .name("bedrock")
+            .time(SystemTime::now())
+            .settings(SigningSettings::default())
+            .build()
+            .context("Failed to build SigV4 signing params")?
+            .into();
+
+        let body = request.body().and_then(|body| body.as_bytes()).unwrap_or_default();
+        let headers = request
+            .headers()
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.to_str().unwrap_or_default()));
+        let signable_request = SignableRequest::new(
+            request.method().as_str(),
+            request.url().as_str(),
+            headers,
+            SignableBody::Bytes(body),
+        )
+        .context("Failed to build signable Bedrock request")?;
+
+        let (instructions, _signature) = sign(signable_request, &signing_params)
+            .context("Failed to sign Bedrock request")?
+            .into_parts();
+
+        for (name, value) in instructions.headers() {
+            request.headers_mut().insert(
+                reqwest::header::HeaderName::from_bytes(name.as_str().as_bytes())?,
+                reqwest::header::HeaderValue::from_bytes(value.as_bytes())?,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Builds the exact JSON body `chat()` would send for `model`/`context`,
+    /// without performing any I/O. Useful for diagnosing why a provider
+    /// rejects a payload, since it reflects the same serialization `chat()`
+    /// uses. Unlike `chat()`, this skips SigV4 signing, so the `authorization`
+    /// header it would have sent is not present.
+    pub fn build_chat_request(
+        &self,
+        model: &ModelId,
+        context: Context,
+    ) -> anyhow::Result<serde_json::Value> {
+        let max_tokens = context.max_tokens.unwrap_or(4000) as u64;
+        let bedrock_model_id = self.bedrock_model_id(model)?.to_string();
+        let body = build_body(context, max_tokens)?;
+
+        let url = Url::parse(&format!(
+            "{}/model/{bedrock_model_id}/invoke-with-response-stream",
+            self.endpoint()
+        ))?;
+
+        Ok(serde_json::json!({
+            "url": url.to_string(),
+            "headers": {
+                "content-type": "application/json",
+                "accept": "application/vnd.amazon.eventstream",
+            },
+            "body": body,
+        }))
+    }
+
+    pub async fn chat(
+        &self,
+        model: &ModelId,
+        context: Context,
+    ) -> ResultStream<ChatCompletionMessage, anyhow::Error> {
+        let max_tokens = context.max_tokens.unwrap_or(4000) as u64;
+        let bedrock_model_id = self.bedrock_model_id(model)?.to_string();
+        let body = serde_json::to_vec(&build_body(context, max_tokens)?)?;
+
+        let url = Url::parse(&format!(
+            "{}/model/{bedrock_model_id}/invoke-with-response-stream",
+            self.endpoint()
+        ))?;
+
+        let mut request = self
+            .client
+            .post(url.clone())
+            .header("content-type", "application/json")
+            .header("accept", "application/vnd.amazon.eventstream")
+            .body(body)
+            .build()
+            .with_context(|| format_http_context(None, "POST", &url))?;
+        self.sign(&mut request)?;
+
+        debug!(url = %url, model = %model, "Connecting Upstream");
+        let response = self
+            .client
+            .execute(request)
+            .await
+            .with_context(|| format_http_context(None, "POST", &url))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.ok();
+            return Err(anyhow::anyhow!("Bedrock request failed"))
+                .with_context(|| match body {
+                    Some(body) => format!("{status} Reason: {body}"),
+                    None => format!("{status} Reason: [Unknown]"),
+                })
+                .with_context(|| format_http_context(Some(status), "POST", &url));
+        }
+
+        let stream = decode_event_stream(response.bytes_stream());
+        Ok(Box::pin(stream))
+    }
+
+    /// Bedrock's model catalog is account/region-scoped and requires a
+    /// separate `bedrock` (not `bedrock-runtime`) API call to enumerate; since
+    /// every model Forge can reach through Bedrock is already named in
+    /// `model_map`, listing is served from that static configuration instead.
+    pub async fn models(&self) -> anyhow::Result<Vec<Model>> {
+        Ok(self
+            .model_map
+            .keys()
+            .cloned()
+            .map(|id| Model {
+                id,
+                name: None,
+                description: None,
+                context_length: None,
+                tools_supported: Some(true),
+                supports_parallel_tool_calls: None,
+                supports_reasoning: None,
+                supports_vision: None,
+                deprecated: None,
+            })
+            .collect())
+    }
+
+    pub async fn embeddings(
+        &self,
+        _model: &ModelId,
+        _inputs: Vec<String>,
+    ) -> anyhow::Result<Vec<Vec<f32>>> {
+        anyhow::bail!("Bedrock does not currently support an embeddings endpoint")
+    }
+}
+
+/// Decodes a raw `application/vnd.amazon.eventstream` byte stream into
+/// individual completion messages, mirroring `crate::utils::ndjson_lines` but
+/// for AWS's binary event-stream framing instead of newline-delimited JSON.
+fn decode_event_stream<S>(
+    byte_stream: S,
+) -> impl Stream<Item = anyhow::Result<ChatCompletionMessage>>
+where
+    S: Stream<Item = reqwest::Result<Bytes>> + Unpin,
+{
+    futures::stream::unfold(
+        (byte_stream, EventStreamDecoder::default(), Vec::<ChatCompletionMessage>::new()),
+        |(mut byte_stream, mut decoder, mut pending)| async move {
+            loop {
+                if let Some(message) = pending.pop() {
+                    return Some((Ok(message), (byte_stream, decoder, pending)));
+                }
+
+                match byte_stream.next().await {
+                    Some(Ok(bytes)) => match decoder.push(&bytes) {
+                        Ok(mut messages) => {
+                            messages.reverse();
+                            pending = messages;
+                        }
+                        Err(err) => return Some((Err(err), (byte_stream, decoder, pending))),
+                    },
+                    Some(Err(err)) => {
+                        return Some((Err(err.into()), (byte_stream, decoder, pending)))
+                    }
+                    None => return None,
+                }
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_app::domain::{Context, ContextMessage};
+
+    use super::*;
+
+    fn create_bedrock(base_url: &str) -> Bedrock {
+        let mut model_map = HashMap::new();
+        model_map.insert(
+            ModelId::new("claude-3-5-sonnet"),
+            "anthropic.claude-3-5-sonnet-20241022-v2:0".to_string(),
+        );
+
+        Bedrock::builder()
+            .client(Client::new())
+            .region("us-east-1".to_string())
+            .base_url(Some(Url::parse(base_url).unwrap()))
+            .model_map(model_map)
+            .access_key_id("AKIAFIXTURE".to_string())
+            .secret_access_key("secret".to_string())
+            .session_token(None)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_chat_sends_sigv4_signing_headers() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock(
+                "POST",
+                "/model/anthropic.claude-3-5-sonnet-20241022-v2%3A0/invoke-with-response-stream",
+            )
+            .match_header("authorization", mockito::Matcher::Regex("AWS4-HMAC-SHA256.*".into()))
+            .match_header("x-amz-date", mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/vnd.amazon.eventstream")
+            .with_body(Vec::<u8>::new())
+            .create_async()
+            .await;
+
+        let bedrock = create_bedrock(&server.url());
+        let context = Context::default().add_message(ContextMessage::user("hi", None));
+        let stream = bedrock
+            .chat(&ModelId::new("claude-3-5-sonnet"), context)
+            .await
+            .unwrap();
+        let _ = stream.collect::<Vec<_>>().await;
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_models_lists_configured_model_map() {
+        let bedrock = create_bedrock("https://bedrock-runtime.us-east-1.amazonaws.com");
+        let models = bedrock.models().await.unwrap();
+
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].id, ModelId::new("claude-3-5-sonnet"));
+    }
+}
@@ -0,0 +1,23 @@
+use forge_app::domain::Context;
+use serde_json::{json, Value};
+
+use crate::anthropic::AnthropicRequest;
+
+/// Bedrock pins the wire format revision in the request body instead of the
+/// `anthropic-version` header the native Anthropic API uses.
+/// ref: <https://docs.aws.amazon.com/bedrock/latest/userguide/model-parameters-anthropic-claude-messages.html>
+const ANTHROPIC_VERSION: &str = "bedrock-2023-05-31";
+
+/// Builds the JSON body for `InvokeModelWithResponseStream`. It's the same
+/// Anthropic Messages API payload the native Anthropic provider sends, minus
+/// `model` (Bedrock takes the model id from the request URL instead) and
+/// with `anthropic_version` spliced in.
+pub fn build_body(context: Context, max_tokens: u64) -> anyhow::Result<Value> {
+    let request = AnthropicRequest::try_from(context)?.max_tokens(max_tokens);
+    let mut body = serde_json::to_value(request)?;
+    if let Value::Object(map) = &mut body {
+        map.remove("model");
+        map.insert("anthropic_version".to_string(), json!(ANTHROPIC_VERSION));
+    }
+    Ok(body)
+}
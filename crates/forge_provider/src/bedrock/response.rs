@@ -0,0 +1,87 @@
+use anyhow::Context as _;
+use aws_smithy_eventstream::frame::Message;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use bytes::Bytes;
+use forge_app::domain::ChatCompletionMessage;
+use serde::Deserialize;
+
+use crate::anthropic::AnthropicEventData;
+
+/// Payload of a `chunk` event: a base64-encoded Anthropic streaming event.
+/// ref: <https://docs.aws.amazon.com/bedrock/latest/userguide/invoke-model-response-stream.html>
+#[derive(Deserialize)]
+struct ChunkPayload {
+    bytes: String,
+}
+
+/// Payload of an `exception`/error-typed event.
+#[derive(Deserialize, Default)]
+struct ExceptionPayload {
+    message: String,
+}
+
+fn header_str(header: &aws_smithy_eventstream::frame::Header) -> Option<String> {
+    header.value().as_string().ok().map(|s| s.as_str().to_string())
+}
+
+/// Incrementally decodes raw `application/vnd.amazon.eventstream` bytes into
+/// Anthropic streaming events. Event-stream frames don't align with HTTP
+/// chunk boundaries, so bytes are buffered across calls to `push` until at
+/// least one full frame is available.
+#[derive(Default)]
+pub struct EventStreamDecoder {
+    buffer: Vec<u8>,
+}
+
+impl EventStreamDecoder {
+    pub fn push(&mut self, bytes: &[u8]) -> anyhow::Result<Vec<ChatCompletionMessage>> {
+        self.buffer.extend_from_slice(bytes);
+        let mut messages = Vec::new();
+
+        loop {
+            let mut cursor = Bytes::from(self.buffer.clone());
+            let frame = match Message::read_from(&mut cursor) {
+                Ok(frame) => frame,
+                // Not enough bytes yet for a full frame; wait for the next chunk.
+                Err(_) => break,
+            };
+            let consumed = self.buffer.len() - cursor.len();
+            self.buffer.drain(..consumed);
+
+            let event_type = frame
+                .headers()
+                .iter()
+                .find(|header| header.name().as_str() == ":event-type")
+                .and_then(header_str);
+
+            match event_type.as_deref() {
+                Some("chunk") => {
+                    let payload: ChunkPayload = serde_json::from_slice(frame.payload())
+                        .context("Failed to parse Bedrock chunk payload")?;
+                    let decoded = BASE64
+                        .decode(payload.bytes)
+                        .context("Failed to base64-decode Bedrock chunk")?;
+                    let event: AnthropicEventData = serde_json::from_slice(&decoded)
+                        .context("Failed to parse Anthropic event inside Bedrock chunk")?;
+                    messages.push(
+                        ChatCompletionMessage::try_from(event)
+                            .context("Failed to build completion message from Bedrock event")?,
+                    );
+                }
+                Some("exception") | Some("modelStreamErrorException")
+                | Some("internalServerException") => {
+                    let payload: ExceptionPayload =
+                        serde_json::from_slice(frame.payload()).unwrap_or_default();
+                    anyhow::bail!("Bedrock stream error: {}", payload.message);
+                }
+                // `:message-type = "event"` frames with any other `:event-type`
+                // (e.g. unrecognized future event types) carry no content we
+                // understand yet - skip them rather than failing the stream.
+                _ => {}
+            }
+        }
+
+        Ok(messages)
+    }
+}
@@ -0,0 +1,155 @@
+//! Synchronous facade over [`Client`], for callers that live outside an
+//! async runtime (CLI entry points, build scripts, FFI boundaries).
+//!
+//! # Pitfalls of nesting runtimes
+//!
+//! [`BlockingClient`] owns a dedicated current-thread Tokio runtime and
+//! blocks the calling thread on it for every call. Constructing or calling a
+//! `BlockingClient` from code that's already running inside a Tokio runtime
+//! panics - Tokio refuses to start or block on a runtime from a thread that's
+//! already driving one ("Cannot start a runtime from within a runtime" /
+//! "Cannot block the current thread from within a runtime"). Use the
+//! ordinary async [`Client`] directly from async code; reach for
+//! `BlockingClient` only at a program's outermost, non-async boundary.
+
+use forge_app::domain::{BoxStream, ChatCompletionMessage, Context, Model, ModelId};
+
+use crate::client::Client;
+
+/// Synchronous facade over [`Client`]. See the module docs for the pitfalls
+/// of mixing this with async code.
+pub struct BlockingClient {
+    client: Client,
+    runtime: tokio::runtime::Runtime,
+}
+
+impl BlockingClient {
+    /// Wraps `client` with a dedicated current-thread Tokio runtime, reused
+    /// across every call made through the returned `BlockingClient` rather
+    /// than spun up fresh each time.
+    pub fn new(client: Client) -> anyhow::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+        Ok(Self { client, runtime })
+    }
+
+    /// Blocking equivalent of [`Client::chat_complete`].
+    pub fn chat_complete(
+        &self,
+        model: &ModelId,
+        context: Context,
+    ) -> anyhow::Result<ChatCompletionMessage> {
+        self.runtime.block_on(self.client.chat_complete(model, context))
+    }
+
+    /// Blocking equivalent of [`Client::models`].
+    pub fn models(&self) -> anyhow::Result<Vec<Model>> {
+        self.runtime.block_on(self.client.models())
+    }
+
+    /// Blocking equivalent of [`Client::model`].
+    pub fn model(&self, model: &ModelId) -> anyhow::Result<Model> {
+        self.runtime.block_on(self.client.model(model))
+    }
+
+    /// Blocking equivalent of [`Client::chat`]: returns an iterator over the
+    /// streamed response instead of an async `Stream`, blocking this
+    /// `BlockingClient`'s runtime to pull one item at a time.
+    pub fn chat(
+        &self,
+        model: &ModelId,
+        context: Context,
+    ) -> anyhow::Result<BlockingChatIter<'_>> {
+        let stream = self.runtime.block_on(self.client.chat(model, context))?;
+        Ok(BlockingChatIter { runtime: &self.runtime, stream })
+    }
+}
+
+/// Iterator returned by [`BlockingClient::chat`]. Each call to `next()`
+/// blocks the calling thread until the next streamed item (or the end of the
+/// stream) is available.
+pub struct BlockingChatIter<'a> {
+    runtime: &'a tokio::runtime::Runtime,
+    stream: BoxStream<ChatCompletionMessage, anyhow::Error>,
+}
+
+impl Iterator for BlockingChatIter<'_> {
+    type Item = anyhow::Result<ChatCompletionMessage>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(tokio_stream::StreamExt::next(&mut self.stream))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_app::domain::RetryConfig;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::mock_provider::MockProvider;
+
+    fn model_id() -> ModelId {
+        ModelId::new("gpt-4")
+    }
+
+    #[test]
+    fn test_chat_complete_blocks_on_a_mock_provider() {
+        let mock = MockProvider::builder().chat_response(vec![ChatCompletionMessage {
+            content: Some(forge_app::domain::Content::part("hello")),
+            ..Default::default()
+        }]);
+        let client = Client::new_mock(mock, std::sync::Arc::new(RetryConfig::default()));
+        let blocking = BlockingClient::new(client).unwrap();
+
+        let actual = blocking.chat_complete(&model_id(), Context::default()).unwrap();
+
+        assert_eq!(actual.content.map(|c| c.as_str().to_string()), Some("hello".to_string()));
+    }
+
+    #[test]
+    fn test_models_blocks_on_a_mock_provider() {
+        let canned = vec![Model {
+            id: model_id(),
+            name: None,
+            description: None,
+            context_length: None,
+            tools_supported: None,
+            supports_parallel_tool_calls: None,
+            supports_reasoning: None,
+            supports_vision: None,
+            deprecated: None,
+        }];
+        let mock = MockProvider::builder().models(canned.clone());
+        let client = Client::new_mock(mock, std::sync::Arc::new(RetryConfig::default()));
+        let blocking = BlockingClient::new(client).unwrap();
+
+        let actual = blocking.models().unwrap();
+
+        assert_eq!(actual.len(), canned.len());
+        assert_eq!(actual[0].id, canned[0].id);
+    }
+
+    #[test]
+    fn test_chat_iterates_streamed_items_one_at_a_time() {
+        let mock = MockProvider::builder().chat_response(vec![
+            ChatCompletionMessage {
+                content: Some(forge_app::domain::Content::part("a")),
+                ..Default::default()
+            },
+            ChatCompletionMessage {
+                content: Some(forge_app::domain::Content::part("b")),
+                ..Default::default()
+            },
+        ]);
+        let client = Client::new_mock(mock, std::sync::Arc::new(RetryConfig::default()));
+        let blocking = BlockingClient::new(client).unwrap();
+
+        let items: Vec<_> = blocking
+            .chat(&model_id(), Context::default())
+            .unwrap()
+            .map(|item| item.unwrap().content.map(|c| c.as_str().to_string()))
+            .collect();
+
+        assert_eq!(items, vec![Some("a".to_string()), Some("b".to_string())]);
+    }
+}
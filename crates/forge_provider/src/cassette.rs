@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::Path;
+
+use forge_app::domain::ChatCompletionMessage;
+use serde::{Deserialize, Serialize};
+
+/// Whether a [`crate::Client`] configured with [`crate::Client::with_cassette`]
+/// records real chat responses to disk or replays previously recorded ones
+/// instead of making a network call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Dispatch the call as normal, then write its full response to the
+    /// cassette file.
+    Record,
+    /// Never dispatch the call; serve a previously recorded response for the
+    /// same request, or fail with [`crate::error::ProviderError::CassetteMiss`].
+    Replay,
+}
+
+/// On-disk representation of every call recorded to a cassette file, keyed by
+/// [`request_key`]. Recording the fully materialized
+/// `Vec<ChatCompletionMessage>` a call resolved to, rather than raw wire
+/// bytes, means replay works uniformly across providers, independent of
+/// whichever wire format produced it.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Cassette {
+    #[serde(default)]
+    entries: HashMap<u64, Vec<ChatCompletionMessage>>,
+}
+
+/// Hashes an outgoing call's operation name, URL, and body into the key
+/// [`Cassette`] entries are recorded and replayed under.
+pub(crate) fn request_key(operation: &str, url: &str, body: &serde_json::Value) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    operation.hash(&mut hasher);
+    url.hash(&mut hasher);
+    body.to_string().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Loads the cassette at `path`. Any I/O or parse failure - missing file,
+/// corrupt JSON - is treated as an empty cassette, matching
+/// `disk_cache::load`'s "no cache is not an error" handling.
+async fn load(path: &Path) -> Cassette {
+    match forge_fs::ForgeFS::read(path).await {
+        Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_default(),
+        Err(_) => Cassette::default(),
+    }
+}
+
+/// Looks up a previously recorded response for `key` in the cassette at
+/// `path`, if one was ever recorded there.
+pub(crate) async fn replay(path: &Path, key: u64) -> Option<Vec<ChatCompletionMessage>> {
+    load(path).await.entries.remove(&key)
+}
+
+/// Records `messages` under `key` in the cassette at `path`, merging with
+/// whatever other entries are already in the file rather than overwriting
+/// them, and replacing any earlier recording for the same `key`.
+pub(crate) async fn record(
+    path: &Path,
+    key: u64,
+    messages: Vec<ChatCompletionMessage>,
+) -> anyhow::Result<()> {
+    let mut cassette = load(path).await;
+    cassette.entries.insert(key, messages);
+    let json = serde_json::to_vec_pretty(&cassette)?;
+    forge_fs::ForgeFS::write(path, json).await
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_app::domain::Content;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_record_then_replay_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("cassette.json");
+        let key = request_key("chat", "https://api.openai.com/v1/chat/completions", &serde_json::json!({"model": "gpt-4"}));
+        let messages = vec![ChatCompletionMessage::assistant(Content::full("hello"))];
+
+        record(&path, key, messages.clone()).await.unwrap();
+        let replayed = replay(&path, key).await.unwrap();
+
+        assert_eq!(replayed, messages);
+    }
+
+    #[tokio::test]
+    async fn test_replay_misses_return_none() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("cassette.json");
+        let key = request_key("chat", "https://api.openai.com/v1/chat/completions", &serde_json::json!({}));
+
+        assert!(replay(&path, key).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_recording_the_same_key_twice_replaces_the_entry() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("cassette.json");
+        let key = request_key("chat", "https://api.openai.com/v1/chat/completions", &serde_json::json!({}));
+
+        record(&path, key, vec![ChatCompletionMessage::assistant(Content::full("first"))])
+            .await
+            .unwrap();
+        record(&path, key, vec![ChatCompletionMessage::assistant(Content::full("second"))])
+            .await
+            .unwrap();
+
+        let replayed = replay(&path, key).await.unwrap();
+        assert_eq!(replayed, vec![ChatCompletionMessage::assistant(Content::full("second"))]);
+    }
+}
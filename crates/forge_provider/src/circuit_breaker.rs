@@ -0,0 +1,218 @@
+use std::time::{Duration, Instant};
+
+use tokio::sync::Mutex;
+
+/// Configuration for [`CircuitBreaker`], installed via
+/// [`crate::Client::with_circuit_breaker`].
+#[derive(Debug, Clone)]
+pub struct CircuitConfig {
+    /// Consecutive failures needed to trip the circuit from closed to open.
+    pub failure_threshold: u32,
+    /// How long a run of consecutive failures may span before it's
+    /// considered stale and the count resets to zero - keeps a handful of
+    /// unrelated failures spread over a long-running client from adding up
+    /// to a trip.
+    pub window: Duration,
+    /// How long the circuit stays open, fast-failing every call, before
+    /// letting a single probe request through to test recovery.
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            window: Duration::from_secs(60),
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Closed,
+    Open,
+    /// Open's cooldown has elapsed and a single probe call has been let
+    /// through; further callers are fast-failed until that probe resolves.
+    HalfOpen,
+}
+
+struct Inner {
+    state: State,
+    consecutive_failures: u32,
+    streak_started_at: Option<Instant>,
+    opened_at: Option<Instant>,
+}
+
+/// Per-provider circuit breaker, installed via
+/// [`crate::Client::with_circuit_breaker`]. Tracks consecutive failures
+/// reported by the caller (via [`Self::record_failure`]); once
+/// `failure_threshold` are seen within `window`, the circuit opens and
+/// [`Self::try_acquire`] fast-fails every call for `cooldown`, after which a
+/// single probe is let through to test whether the provider has recovered.
+/// Cloning the owning `Client` shares the same breaker (it's stored behind
+/// an `Arc`), so state holds across every clone rather than resetting per
+/// instance.
+pub struct CircuitBreaker {
+    config: CircuitConfig,
+    inner: Mutex<Inner>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitConfig) -> Self {
+        Self {
+            config,
+            inner: Mutex::new(Inner {
+                state: State::Closed,
+                consecutive_failures: 0,
+                streak_started_at: None,
+                opened_at: None,
+            }),
+        }
+    }
+
+    /// Call before issuing a request. Returns `true` if the call should
+    /// proceed (circuit closed, or this is the one probe let through during
+    /// half-open) and `false` if it should fast-fail instead.
+    pub async fn try_acquire(&self) -> bool {
+        let mut inner = self.inner.lock().await;
+        match inner.state {
+            State::Closed => true,
+            State::Open => {
+                let opened_at = inner.opened_at.expect("opened_at set when entering Open");
+                if opened_at.elapsed() >= self.config.cooldown {
+                    inner.state = State::HalfOpen;
+                    true
+                } else {
+                    false
+                }
+            }
+            State::HalfOpen => false,
+        }
+    }
+
+    /// Reports a successful call. Closes the circuit (from either half-open
+    /// or closed) and resets the failure streak.
+    pub async fn record_success(&self) {
+        let mut inner = self.inner.lock().await;
+        inner.state = State::Closed;
+        inner.consecutive_failures = 0;
+        inner.streak_started_at = None;
+    }
+
+    /// Reports a failed call. A failure during the half-open probe reopens
+    /// the circuit immediately; a failure while closed extends (or, if
+    /// `window` has elapsed since the streak began, restarts) the
+    /// consecutive-failure count, tripping the circuit open once
+    /// `failure_threshold` is reached.
+    pub async fn record_failure(&self) {
+        let mut inner = self.inner.lock().await;
+        match inner.state {
+            State::HalfOpen => {
+                inner.state = State::Open;
+                inner.opened_at = Some(Instant::now());
+            }
+            State::Closed => {
+                let now = Instant::now();
+                let stale = inner
+                    .streak_started_at
+                    .is_some_and(|started| now.duration_since(started) > self.config.window);
+                if inner.streak_started_at.is_none() || stale {
+                    inner.streak_started_at = Some(now);
+                    inner.consecutive_failures = 1;
+                } else {
+                    inner.consecutive_failures += 1;
+                }
+                if inner.consecutive_failures >= self.config.failure_threshold {
+                    inner.state = State::Open;
+                    inner.opened_at = Some(now);
+                }
+            }
+            State::Open => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(failure_threshold: u32, window: Duration, cooldown: Duration) -> CircuitBreaker {
+        CircuitBreaker::new(CircuitConfig { failure_threshold, window, cooldown })
+    }
+
+    #[tokio::test]
+    async fn test_closed_circuit_always_admits_calls() {
+        let breaker = fixture(3, Duration::from_secs(60), Duration::from_secs(10));
+        for _ in 0..10 {
+            assert!(breaker.try_acquire().await);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_opens_after_consecutive_failures_reach_the_threshold() {
+        let breaker = fixture(3, Duration::from_secs(60), Duration::from_secs(10));
+
+        breaker.record_failure().await;
+        breaker.record_failure().await;
+        assert!(breaker.try_acquire().await, "still closed before the 3rd failure");
+
+        breaker.record_failure().await;
+        assert!(!breaker.try_acquire().await, "opens on the 3rd consecutive failure");
+    }
+
+    #[tokio::test]
+    async fn test_a_success_resets_the_consecutive_failure_streak() {
+        let breaker = fixture(3, Duration::from_secs(60), Duration::from_secs(10));
+
+        breaker.record_failure().await;
+        breaker.record_failure().await;
+        breaker.record_success().await;
+        breaker.record_failure().await;
+        breaker.record_failure().await;
+
+        assert!(breaker.try_acquire().await, "streak was reset by the success in between");
+    }
+
+    #[tokio::test]
+    async fn test_half_opens_after_cooldown_and_admits_a_single_probe() {
+        let breaker = fixture(1, Duration::from_secs(60), Duration::from_millis(20));
+
+        breaker.record_failure().await;
+        assert!(!breaker.try_acquire().await, "open immediately after tripping");
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+
+        assert!(breaker.try_acquire().await, "cooldown elapsed, probe admitted");
+        assert!(!breaker.try_acquire().await, "a second caller is fast-failed during half-open");
+    }
+
+    #[tokio::test]
+    async fn test_half_open_success_closes_the_circuit() {
+        let breaker = fixture(1, Duration::from_secs(60), Duration::from_millis(20));
+
+        breaker.record_failure().await;
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(breaker.try_acquire().await);
+
+        breaker.record_success().await;
+
+        assert!(breaker.try_acquire().await, "closed again after the probe succeeded");
+        assert!(breaker.try_acquire().await, "and stays closed for the next caller too");
+    }
+
+    #[tokio::test]
+    async fn test_half_open_failure_reopens_the_circuit_for_another_full_cooldown() {
+        let breaker = fixture(1, Duration::from_secs(60), Duration::from_millis(20));
+
+        breaker.record_failure().await;
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(breaker.try_acquire().await);
+
+        breaker.record_failure().await;
+        assert!(!breaker.try_acquire().await, "failed probe reopens the circuit");
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(breaker.try_acquire().await, "reopened circuit still honors its own cooldown");
+    }
+}
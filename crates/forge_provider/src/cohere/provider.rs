@@ -0,0 +1,222 @@
+use anyhow::Context as _;
+use derive_builder::Builder;
+use forge_app::domain::{
+    ChatCompletionMessage, Context as ChatContext, Model, ModelId, ResultStream,
+};
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use reqwest::{Client, Url};
+use tokio_stream::StreamExt;
+use tracing::debug;
+
+use super::request::ChatRequest;
+use super::response::{ListModelResponse, StreamEvent};
+use crate::error::Error;
+use crate::utils::{format_http_context, ndjson_lines};
+
+#[derive(Clone, Builder)]
+pub struct Cohere {
+    client: Client,
+    api_key: String,
+    base_url: Url,
+}
+
+impl Cohere {
+    pub fn builder() -> CohereBuilder {
+        CohereBuilder::default()
+    }
+
+    fn url(&self, path: &str) -> anyhow::Result<Url> {
+        if path.contains("://") || path.contains("..") {
+            anyhow::bail!("Invalid path: Contains forbidden patterns");
+        }
+
+        let path = path.trim_start_matches('/');
+
+        self.base_url
+            .join(path)
+            .with_context(|| format!("Failed to append {} to base URL: {}", path, self.base_url))
+    }
+
+    fn headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", self.api_key)).unwrap(),
+        );
+        headers
+    }
+
+    /// Builds the exact JSON body `chat()` would send for `model`/`context`,
+    /// without performing any I/O. Useful for diagnosing why a provider
+    /// rejects a payload, since it reflects the same serialization `chat()`
+    /// uses.
+    pub fn build_chat_request(
+        &self,
+        model: &ModelId,
+        context: ChatContext,
+    ) -> anyhow::Result<serde_json::Value> {
+        let request = ChatRequest::new(model.as_str().to_string(), context);
+        let url = self.url("v1/chat")?;
+
+        Ok(serde_json::json!({
+            "url": url.to_string(),
+            "headers": {},
+            "body": request,
+        }))
+    }
+
+    pub async fn chat(
+        &self,
+        model: &ModelId,
+        context: ChatContext,
+    ) -> ResultStream<ChatCompletionMessage, anyhow::Error> {
+        let request = ChatRequest::new(model.as_str().to_string(), context);
+        let url = self.url("v1/chat")?;
+
+        debug!(url = %url, model = %model, "Connecting Upstream");
+
+        let response = self
+            .client
+            .post(url.clone())
+            .headers(self.headers())
+            .json(&request)
+            .send()
+            .await
+            .with_context(|| format_http_context(None, "POST", &url))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.ok();
+            return Err(Error::InvalidStatusCode(status.as_u16()))
+                .with_context(|| match body {
+                    Some(body) => format!("{status} Reason: {body}"),
+                    None => format!("{status} Reason: [Unknown]"),
+                })
+                .with_context(|| format_http_context(Some(status), "POST", &url));
+        }
+
+        let stream = ndjson_lines(response.bytes_stream()).map(move |line| {
+            line.with_context(|| format_http_context(None, "POST", &url))
+                .and_then(|line| {
+                    serde_json::from_str::<StreamEvent>(&line)
+                        .with_context(|| format!("Failed to parse Cohere event: {line}"))
+                })
+                .map(StreamEvent::into_message)
+        });
+
+        Ok(Box::pin(stream.filter_map(|message| match message {
+            Ok(Some(message)) => Some(Ok(message)),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        })))
+    }
+
+    pub async fn models(&self) -> anyhow::Result<Vec<Model>> {
+        let url = self.url("v1/models")?;
+        debug!(url = %url, "Fetching models");
+
+        let response = self
+            .client
+            .get(url.clone())
+            .headers(self.headers())
+            .send()
+            .await
+            .with_context(|| format_http_context(None, "GET", &url))
+            .with_context(|| "Failed to fetch models")?;
+
+        let status = response.status();
+        let ctx_msg = format_http_context(Some(status), "GET", &url);
+        let text = response
+            .text()
+            .await
+            .with_context(|| ctx_msg.clone())
+            .with_context(|| "Failed to decode response into text")?;
+
+        if !status.is_success() {
+            return Err(anyhow::anyhow!(text))
+                .with_context(|| ctx_msg)
+                .with_context(|| "Failed to fetch the models");
+        }
+
+        let response: ListModelResponse = serde_json::from_str(&text)
+            .with_context(|| ctx_msg)
+            .with_context(|| "Failed to deserialize models response")?;
+
+        Ok(response.models.into_iter().map(Into::into).collect())
+    }
+
+    pub async fn embeddings(
+        &self,
+        _model: &ModelId,
+        _inputs: Vec<String>,
+    ) -> anyhow::Result<Vec<Vec<f32>>> {
+        anyhow::bail!("Cohere embeddings are not yet supported by this client")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockito::Server;
+    use pretty_assertions::assert_eq;
+    use reqwest::Client;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_chat_targets_v1_chat() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/chat")
+            .with_status(200)
+            .with_header("content-type", "application/x-ndjson")
+            .with_body(
+                r#"{"event_type":"text-generation","text":"Hi"}
+{"event_type":"stream-end","finish_reason":"COMPLETE","response":{}}
+"#,
+            )
+            .create_async()
+            .await;
+
+        let cohere = Cohere::builder()
+            .client(Client::new())
+            .api_key("test-key".to_string())
+            .base_url(Url::parse(&format!("{}/", server.url())).unwrap())
+            .build()
+            .unwrap();
+
+        let messages: Vec<_> = cohere
+            .chat(&ModelId::new("command-r"), ChatContext::default())
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        mock.assert_async().await;
+        assert_eq!(messages.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_models_targets_v1_models() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v1/models")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"models":[{"name":"command-r"}]}"#)
+            .create_async()
+            .await;
+
+        let cohere = Cohere::builder()
+            .client(Client::new())
+            .api_key("test-key".to_string())
+            .base_url(Url::parse(&format!("{}/", server.url())).unwrap())
+            .build()
+            .unwrap();
+
+        let models = cohere.models().await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].id, ModelId::new("command-r"));
+    }
+}
@@ -0,0 +1,156 @@
+use forge_app::domain::{Context, ContextMessage, Role, ToolOutput, ToolValue};
+use serde::Serialize;
+
+/// Body for Cohere's `/v1/chat`. Unlike the OpenAI-shaped providers, Cohere
+/// takes the latest turn as a standalone `message` field, everything before
+/// it as `chat_history`, and has no per-turn system role - system messages
+/// are instead merged into a single `preamble`.
+#[derive(Debug, Serialize)]
+pub struct ChatRequest {
+    pub model: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preamble: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub chat_history: Vec<ChatHistoryMessage>,
+    pub stream: bool,
+    /// Grounding documents for retrieval-augmented generation. Cohere
+    /// accepts arbitrary string-keyed objects here (e.g. `title`/`snippet`),
+    /// so this is left as raw JSON rather than a fixed struct.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub documents: Option<Vec<serde_json::Value>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChatHistoryMessage {
+    pub role: CohereRole,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum CohereRole {
+    User,
+    Chatbot,
+}
+
+impl ChatRequest {
+    pub fn new(model: String, context: Context) -> Self {
+        let mut messages = context.messages;
+        let last = messages.pop();
+
+        let mut preamble_parts = Vec::new();
+        let mut chat_history = Vec::new();
+        for message in messages {
+            match message {
+                ContextMessage::Text(text) if text.role == Role::System => {
+                    preamble_parts.push(text.content);
+                }
+                ContextMessage::Text(text) => {
+                    let role = match text.role {
+                        Role::Assistant => CohereRole::Chatbot,
+                        _ => CohereRole::User,
+                    };
+                    chat_history.push(ChatHistoryMessage { role, message: text.content });
+                }
+                ContextMessage::Tool(tool_result) => chat_history.push(ChatHistoryMessage {
+                    role: CohereRole::User,
+                    message: tool_output_to_text(&tool_result.output),
+                }),
+                ContextMessage::Image(_) => {
+                    // Image parts are not yet translated for Cohere.
+                }
+            }
+        }
+
+        let message = match last {
+            Some(ContextMessage::Text(text)) if text.role == Role::System => {
+                preamble_parts.push(text.content);
+                String::new()
+            }
+            Some(ContextMessage::Text(text)) => text.content,
+            Some(ContextMessage::Tool(tool_result)) => tool_output_to_text(&tool_result.output),
+            Some(ContextMessage::Image(_)) | None => String::new(),
+        };
+
+        ChatRequest {
+            model,
+            message,
+            preamble: (!preamble_parts.is_empty()).then(|| preamble_parts.join("\n\n")),
+            chat_history,
+            stream: true,
+            documents: None,
+        }
+    }
+
+    /// Attaches grounding documents for retrieval-augmented generation. A
+    /// no-op if `documents` is empty, so callers can pass through whatever
+    /// they have on hand without a separate branch.
+    pub fn documents(mut self, documents: Vec<serde_json::Value>) -> Self {
+        self.documents = (!documents.is_empty()).then_some(documents);
+        self
+    }
+}
+
+fn tool_output_to_text(output: &ToolOutput) -> String {
+    output
+        .values
+        .iter()
+        .filter_map(|value| match value {
+            ToolValue::Text(text) => Some(text.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_app::domain::ModelId;
+
+    use super::*;
+
+    #[test]
+    fn test_system_message_becomes_preamble() {
+        let context = Context::default()
+            .add_message(ContextMessage::system("You are helpful."))
+            .add_message(ContextMessage::user("Hi", ModelId::new("command-r").into()));
+
+        let request = ChatRequest::new("command-r".to_string(), context);
+
+        assert_eq!(request.preamble, Some("You are helpful.".to_string()));
+        assert_eq!(request.message, "Hi");
+        assert!(request.chat_history.is_empty());
+    }
+
+    #[test]
+    fn test_prior_turns_become_chat_history() {
+        let context = Context::default()
+            .add_message(ContextMessage::user("Hi", ModelId::new("command-r").into()))
+            .add_message(ContextMessage::assistant("Hello!", None, None))
+            .add_message(ContextMessage::user(
+                "How are you?",
+                ModelId::new("command-r").into(),
+            ));
+
+        let request = ChatRequest::new("command-r".to_string(), context);
+
+        assert_eq!(request.chat_history.len(), 2);
+        assert_eq!(request.message, "How are you?");
+    }
+
+    #[test]
+    fn test_empty_documents_are_dropped() {
+        let request =
+            ChatRequest::new("command-r".to_string(), Context::default()).documents(vec![]);
+        assert!(request.documents.is_none());
+    }
+
+    #[test]
+    fn test_documents_are_attached_when_present() {
+        let doc = serde_json::json!({ "title": "doc", "snippet": "content" });
+        let request = ChatRequest::new("command-r".to_string(), Context::default())
+            .documents(vec![doc.clone()]);
+        assert_eq!(request.documents, Some(vec![doc]));
+    }
+}
@@ -0,0 +1,171 @@
+use forge_app::domain::{ChatCompletionMessage, Content, FinishReason, Model, ModelId, Usage};
+use serde::Deserialize;
+
+/// One line of Cohere's streamed `/v1/chat` response. Cohere streams
+/// newline-delimited JSON objects (not SSE), each tagged with an
+/// `event_type`; only `text-generation` and `stream-end` carry content
+/// callers care about, the rest (`stream-start`, `search-queries-generation`,
+/// `citation-generation`, ...) are parsed but produce no message.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "event_type", rename_all = "kebab-case")]
+pub enum StreamEvent {
+    TextGeneration { text: String },
+    StreamEnd { finish_reason: Option<String>, response: StreamEndResponse },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StreamEndResponse {
+    #[serde(default)]
+    pub meta: Option<Meta>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Meta {
+    #[serde(default)]
+    pub billed_units: Option<BilledUnits>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BilledUnits {
+    #[serde(default)]
+    pub input_tokens: f64,
+    #[serde(default)]
+    pub output_tokens: f64,
+}
+
+impl StreamEvent {
+    /// Converts this event into a [`ChatCompletionMessage`], or `None` for
+    /// event types that carry no content (`stream-start`,
+    /// `search-queries-generation`, etc).
+    pub fn into_message(self) -> Option<ChatCompletionMessage> {
+        match self {
+            StreamEvent::TextGeneration { text } => Some(ChatCompletionMessage {
+                content: Some(Content::part(text)),
+                reasoning: None,
+                reasoning_details: None,
+                tool_calls: vec![],
+                finish_reason: None,
+                usage: None,
+                request_id: None,
+                upstream_provider: None,
+                logprobs: None,
+                system_fingerprint: None,
+            }),
+            StreamEvent::StreamEnd { finish_reason, response } => {
+                let finish_reason = finish_reason.and_then(|reason| match reason.as_str() {
+                    "COMPLETE" => Some(FinishReason::Stop),
+                    "MAX_TOKENS" => Some(FinishReason::Length),
+                    "ERROR_TOXIC" | "ERROR_LIMIT" => Some(FinishReason::ContentFilter),
+                    other => Some(FinishReason::Other(other.to_string())),
+                });
+                let usage = response.meta.and_then(|meta| meta.billed_units).map(|units| Usage {
+                    prompt_tokens: units.input_tokens as usize,
+                    completion_tokens: units.output_tokens as usize,
+                    total_tokens: (units.input_tokens + units.output_tokens) as usize,
+                    estimated_tokens: 0,
+                    cached_tokens: 0,
+                    cache_write_tokens: 0,
+                    reasoning_tokens: 0,
+                    cost: None,
+                });
+                Some(ChatCompletionMessage {
+                    content: None,
+                    reasoning: None,
+                    reasoning_details: None,
+                    tool_calls: vec![],
+                    finish_reason,
+                    usage,
+                    request_id: None,
+                    upstream_provider: None,
+                    logprobs: None,
+                    system_fingerprint: None,
+                })
+            }
+            StreamEvent::Other => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListModelResponse {
+    #[serde(default)]
+    pub models: Vec<CohereModel>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CohereModel {
+    pub name: String,
+    #[serde(default)]
+    pub context_length: Option<u64>,
+}
+
+impl From<CohereModel> for Model {
+    fn from(value: CohereModel) -> Self {
+        Model {
+            id: ModelId::new(value.name),
+            name: None,
+            description: None,
+            context_length: value.context_length,
+            tools_supported: None,
+            supports_parallel_tool_calls: None,
+            supports_reasoning: None,
+            supports_vision: None,
+            deprecated: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_generation_event_carries_content() {
+        let event: StreamEvent =
+            serde_json::from_str(r#"{"event_type":"text-generation","text":"Hello"}"#).unwrap();
+        let message = event.into_message().unwrap();
+        assert_eq!(message.content.as_ref().map(|c| c.as_str()), Some("Hello"));
+    }
+
+    #[test]
+    fn test_stream_start_event_carries_no_content() {
+        let event: StreamEvent =
+            serde_json::from_str(r#"{"event_type":"stream-start","generation_id":"abc"}"#)
+                .unwrap();
+        assert!(event.into_message().is_none());
+    }
+
+    #[test]
+    fn test_stream_end_event_carries_usage_and_finish_reason() {
+        let event: StreamEvent = serde_json::from_str(
+            r#"{
+                "event_type": "stream-end",
+                "finish_reason": "COMPLETE",
+                "response": {
+                    "meta": { "billed_units": { "input_tokens": 5, "output_tokens": 3 } }
+                }
+            }"#,
+        )
+        .unwrap();
+        let message = event.into_message().unwrap();
+        assert_eq!(message.finish_reason, Some(FinishReason::Stop));
+        let usage = message.usage.unwrap();
+        assert_eq!(usage.prompt_tokens, 5);
+        assert_eq!(usage.completion_tokens, 3);
+    }
+
+    #[test]
+    fn test_stream_end_event_falls_back_to_other_for_unrecognized_finish_reason() {
+        let event: StreamEvent = serde_json::from_str(
+            r#"{"event_type":"stream-end","finish_reason":"USER_CANCEL","response":{}}"#,
+        )
+        .unwrap();
+        let message = event.into_message().unwrap();
+        assert_eq!(
+            message.finish_reason,
+            Some(FinishReason::Other("USER_CANCEL".to_string()))
+        );
+    }
+}
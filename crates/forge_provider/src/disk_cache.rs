@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+use forge_app::domain::{Model, ModelId};
+use serde::{Deserialize, Serialize};
+
+/// On-disk representation of the models cache, keyed by provider so that two
+/// `Client`s pointed at the same cache file (e.g. one for OpenAI, one for
+/// Anthropic) don't clobber each other's entries.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DiskCache {
+    #[serde(default)]
+    providers: HashMap<String, ProviderEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ProviderEntry {
+    models: Vec<Model>,
+    cached_at: DateTime<Utc>,
+}
+
+/// Loads the cached models for `provider_key` from `path`, if the file
+/// exists, parses cleanly, and has an entry for that provider. `cached_at` is
+/// a wall-clock timestamp on disk, so it's translated into an equivalent
+/// (backdated) [`Instant`] here to slot directly into `Client`'s in-memory
+/// cache, which tracks staleness with `Instant` for monotonicity.
+///
+/// Any I/O or parse failure - missing file, corrupt JSON, an entry from a
+/// future version of this format - is treated the same as "no cache", so a
+/// partial or corrupt file never blocks startup; the caller just refetches.
+pub(crate) async fn load(
+    path: &Path,
+    provider_key: &str,
+) -> Option<HashMap<ModelId, (Model, Instant)>> {
+    let bytes = forge_fs::ForgeFS::read(path).await.ok()?;
+    let cache: DiskCache = serde_json::from_slice(&bytes).ok()?;
+    let entry = cache.providers.get(provider_key)?;
+
+    let age = Utc::now()
+        .signed_duration_since(entry.cached_at)
+        .to_std()
+        .ok()?;
+    let cached_at = Instant::now().checked_sub(age)?;
+
+    Some(
+        entry
+            .models
+            .iter()
+            .cloned()
+            .map(|model| (model.id.clone(), (model, cached_at)))
+            .collect(),
+    )
+}
+
+/// Persists `models` for `provider_key` to `path`, merging with whatever
+/// other providers' entries are already in the file rather than overwriting
+/// them. A missing or corrupt existing file is treated as an empty cache
+/// rather than an error, so a prior partial write never blocks future saves.
+pub(crate) async fn save(path: &Path, provider_key: &str, models: &[Model]) -> anyhow::Result<()> {
+    let mut cache = match forge_fs::ForgeFS::read(path).await {
+        Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_default(),
+        Err(_) => DiskCache::default(),
+    };
+
+    cache.providers.insert(
+        provider_key.to_string(),
+        ProviderEntry { models: models.to_vec(), cached_at: Utc::now() },
+    );
+
+    let json = serde_json::to_vec_pretty(&cache)?;
+    forge_fs::ForgeFS::write(path, json).await
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn sample_model(id: &str) -> Model {
+        Model {
+            id: ModelId::new(id),
+            name: None,
+            description: None,
+            context_length: None,
+            tools_supported: None,
+            supports_parallel_tool_calls: None,
+            supports_reasoning: None,
+            supports_vision: None,
+            deprecated: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_through_disk() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("models_cache.json");
+        let models = vec![sample_model("gpt-4"), sample_model("gpt-4-mini")];
+
+        save(&path, "https://api.openai.com/v1/", &models)
+            .await
+            .unwrap();
+        let loaded = load(&path, "https://api.openai.com/v1/").await.unwrap();
+
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded.get(&ModelId::new("gpt-4")).unwrap().0, models[0]);
+    }
+
+    #[tokio::test]
+    async fn test_different_providers_do_not_clobber_each_other() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("models_cache.json");
+
+        save(&path, "https://api.openai.com/v1/", &[sample_model("gpt-4")])
+            .await
+            .unwrap();
+        save(&path, "https://api.anthropic.com/v1/", &[sample_model("claude-3")])
+            .await
+            .unwrap();
+
+        let openai = load(&path, "https://api.openai.com/v1/").await.unwrap();
+        let anthropic = load(&path, "https://api.anthropic.com/v1/").await.unwrap();
+
+        assert_eq!(openai.len(), 1);
+        assert_eq!(anthropic.len(), 1);
+        assert!(openai.contains_key(&ModelId::new("gpt-4")));
+        assert!(anthropic.contains_key(&ModelId::new("claude-3")));
+    }
+
+    #[tokio::test]
+    async fn test_load_missing_file_returns_none() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("does-not-exist.json");
+
+        assert!(load(&path, "https://api.openai.com/v1/").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_load_corrupt_file_returns_none() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("models_cache.json");
+        forge_fs::ForgeFS::write(&path, b"not valid json".to_vec())
+            .await
+            .unwrap();
+
+        assert!(load(&path, "https://api.openai.com/v1/").await.is_none());
+    }
+}
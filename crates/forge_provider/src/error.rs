@@ -1,7 +1,9 @@
 use std::collections::BTreeMap;
 use std::fmt::Formatter;
+use std::time::Duration;
 
 use derive_setters::Setters;
+use forge_app::domain::ModelId;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -26,6 +28,116 @@ pub enum Error {
     InvalidStatusCode(u16),
 }
 
+/// A typed classification of provider failures, so callers (and `into_retry`)
+/// can match on the *kind* of failure instead of string-sniffing a generic
+/// `anyhow::Error`. Providers that want this level of detail should return
+/// `ProviderError` from their fallible paths; it converts into `anyhow::Error`
+/// for free via `std::error::Error`, so existing `Result<_, anyhow::Error>`
+/// signatures keep working through `?`.
+#[derive(Debug, Error)]
+pub enum ProviderError {
+    #[error("Rate limited{}", .retry_after.map(|d| format!(", retry after {d:?}")).unwrap_or_default())]
+    RateLimited { retry_after: Option<Duration> },
+
+    #[error("Unauthorized: invalid or missing API key")]
+    Unauthorized,
+
+    #[error("Model not found: {0}")]
+    ModelNotFound(ModelId),
+
+    #[error("Request timed out during the {phase} phase")]
+    Timeout { phase: TimeoutPhase },
+
+    #[error("Timed out waiting for the first streamed chunk")]
+    FirstTokenTimeout,
+
+    #[error("Timed out waiting for the next streamed chunk")]
+    InterTokenTimeout,
+
+    #[error("Upstream error (status {status}): {body}")]
+    Upstream { status: u16, body: String },
+
+    #[error("Network error: {0}")]
+    Network(#[from] reqwest::Error),
+
+    #[error("Prompt is too large for this model: needed ~{needed} tokens, limit is {limit}")]
+    ContextLengthExceeded { needed: u64, limit: u64 },
+
+    #[error("Request too large: {size} bytes exceeds the {limit} byte limit")]
+    RequestTooLarge { size: u64, limit: u64 },
+
+    #[error("Too many stop sequences: {count} exceeds this provider's limit of {limit}")]
+    TooManyStopSequences { count: usize, limit: usize },
+
+    #[error("Provider response did not parse as valid JSON for a JSON-mode request: {content}")]
+    MalformedJsonResponse { content: String },
+
+    #[error("No default model set; call Client::with_default_model or pass a model id explicitly")]
+    NoDefaultModel,
+
+    #[error("Upstream reported an in-band error matching pattern {pattern:?}: {body}")]
+    InBandError { pattern: String, body: String },
+
+    #[error("Model {model} does not support image inputs, but the context includes one")]
+    VisionNotSupported { model: ModelId },
+
+    #[error("Raw SSE events are not exposed for this provider")]
+    RawEventsUnsupported,
+
+    #[error("Keepalive-aware streaming is not exposed for this provider")]
+    KeepAliveEventsUnsupported,
+
+    #[error("Client is shutting down and no longer accepts new requests")]
+    ShuttingDown,
+
+    #[error("No cassette recording found for model {model}")]
+    CassetteMiss { model: ModelId },
+
+    #[error("Circuit breaker is open for this provider; refusing to send new requests")]
+    CircuitOpen,
+}
+
+/// Which phase of an HTTP request a timeout was detected in, so callers can
+/// tell an overloaded provider (slow to respond once connected) apart from
+/// an unreachable one (can't even connect), rather than seeing an opaque
+/// timeout in either case. Derived from [`reqwest::Error::is_connect`] and
+/// [`reqwest::Error::is_timeout`] by [`crate::retry::into_retry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeoutPhase {
+    /// The TCP/TLS connect attempt itself failed or timed out.
+    Connect,
+    /// A connection was established, but no response (or, for a streamed
+    /// chat, a subsequent chunk) arrived within the read deadline.
+    Read,
+    /// Timed out waiting for an idle connection from the pool. Reserved for
+    /// future use: `reqwest` doesn't currently surface this distinctly from
+    /// a `Read` timeout, so it's never produced today.
+    Pool,
+}
+
+impl std::fmt::Display for TimeoutPhase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeoutPhase::Connect => write!(f, "connect"),
+            TimeoutPhase::Read => write!(f, "read"),
+            TimeoutPhase::Pool => write!(f, "pool"),
+        }
+    }
+}
+
+impl ProviderError {
+    /// Classifies an HTTP status code (plus an optional `Retry-After` header
+    /// value) into a `ProviderError`. `Upstream` is the fallback for any
+    /// status not otherwise recognized.
+    pub fn from_status(status: u16, body: String, retry_after: Option<Duration>) -> Self {
+        match status {
+            401 | 403 => ProviderError::Unauthorized,
+            429 => ProviderError::RateLimited { retry_after },
+            _ => ProviderError::Upstream { status, body },
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum ErrorCode {
@@ -49,6 +161,15 @@ impl ErrorCode {
     }
 }
 
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorCode::String(s) => write!(f, "{s}"),
+            ErrorCode::Number(n) => write!(f, "{n}"),
+        }
+    }
+}
+
 #[derive(Default, Debug, Deserialize, Serialize, Clone, Setters)]
 #[setters(strip_option)]
 pub struct ErrorResponse {
@@ -114,6 +235,56 @@ impl std::fmt::Display for AnthropicErrorResponse {
     }
 }
 
+/// Anthropic's non-streaming HTTP error body shape, e.g.
+/// `{"type": "error", "error": {"type": "invalid_request_error", "message":
+/// "..."}}`. Distinct from [`AnthropicErrorResponse`], which only models the
+/// `overloaded_error` case seen on the SSE error event; this one captures the
+/// `type`/`message` pair generically for any error Anthropic might return.
+#[derive(Debug, Deserialize)]
+struct AnthropicErrorBody {
+    #[serde(rename = "type")]
+    type_: String,
+    error: AnthropicErrorDetail,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicErrorDetail {
+    #[serde(rename = "type")]
+    type_: String,
+    message: String,
+}
+
+/// Parses a non-2xx HTTP response body into a human-readable message,
+/// preferring the provider's own error detail over the raw JSON. Recognizes
+/// Anthropic's `{"type": "error", "error": {...}}` shape (the top-level
+/// `type` field is what disambiguates it from OpenAI's shape, which has none)
+/// and OpenAI's `{"error": {"message": ..., "code": ...}}` shape; anything
+/// else is returned unchanged.
+pub fn describe_error_body(body: &str) -> String {
+    if let Ok(anthropic) = serde_json::from_str::<AnthropicErrorBody>(body) {
+        if anthropic.type_ == "error" {
+            return format!("{}: {}", anthropic.error.type_, anthropic.error.message);
+        }
+    }
+
+    if let Ok(response) = serde_json::from_str::<ErrorResponse>(body) {
+        let message = response
+            .error
+            .as_ref()
+            .and_then(|error| error.message.clone())
+            .or(response.message.clone());
+
+        if let Some(message) = message {
+            return match response.get_code_deep() {
+                Some(code) => format!("{message} ({code})"),
+                None => message,
+            };
+        }
+    }
+
+    body.to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -219,4 +390,35 @@ mod tests {
         let expected_code = ErrorCode::Number(500);
         assert_eq!(actual, Some(&expected_code));
     }
+
+    #[test]
+    fn test_describe_error_body_openai_shape() {
+        let body = r#"{"error":{"message":"context length exceeded","code":"context_length_exceeded"}}"#;
+        let actual = describe_error_body(body);
+        assert_eq!(actual, "context length exceeded (context_length_exceeded)");
+    }
+
+    #[test]
+    fn test_describe_error_body_openai_shape_without_code() {
+        let body = r#"{"error":{"message":"invalid api key"}}"#;
+        let actual = describe_error_body(body);
+        assert_eq!(actual, "invalid api key");
+    }
+
+    #[test]
+    fn test_describe_error_body_anthropic_shape() {
+        let body = r#"{"type":"error","error":{"type":"invalid_request_error","message":"max_tokens: 8192 > 4096, which is the maximum allowed"}}"#;
+        let actual = describe_error_body(body);
+        assert_eq!(
+            actual,
+            "invalid_request_error: max_tokens: 8192 > 4096, which is the maximum allowed"
+        );
+    }
+
+    #[test]
+    fn test_describe_error_body_unknown_shape_falls_back_to_raw_body() {
+        let body = "<html>502 Bad Gateway</html>";
+        let actual = describe_error_body(body);
+        assert_eq!(actual, body);
+    }
 }
@@ -13,6 +13,29 @@ pub struct Model {
     pub top_provider: Option<TopProvider>,
     pub per_request_limits: Option<serde_json::Value>,
     pub supported_parameters: Option<Vec<String>>,
+    /// Mistral's `/models` endpoint reports context length and supported
+    /// features through these two fields instead of `context_length` and
+    /// `supported_parameters`.
+    pub max_context_length: Option<u64>,
+    pub capabilities: Option<MistralCapabilities>,
+    /// Set by gateways (e.g. OpenRouter) that mark individual models for
+    /// retirement directly in `/models` metadata rather than via a header.
+    pub deprecated: Option<Deprecated>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Deprecated {
+    pub sunset_date: Option<String>,
+    pub replacement: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct MistralCapabilities {
+    pub completion_chat: Option<bool>,
+    pub completion_fim: Option<bool>,
+    pub function_calling: Option<bool>,
+    pub fine_tuning: Option<bool>,
+    pub vision: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -40,4 +63,9 @@ pub struct TopProvider {
 #[derive(Debug, Deserialize, Clone, Serialize)]
 pub struct ListModelResponse {
     pub data: Vec<Model>,
+    /// Set by gateways that paginate `/models` (cursor-following via
+    /// `after`). `false` for a provider that returns the full list in one
+    /// response and doesn't set the field at all.
+    #[serde(default)]
+    pub has_more: bool,
 }
@@ -1,26 +1,57 @@
+use std::time::Duration;
+
 use anyhow::{Context as _, Result};
 use derive_builder::Builder;
 use forge_app::domain::{
-    ChatCompletionMessage, Context as ChatContext, ModelId, Provider, ResultStream,
+    ChatCompletionMessage, ChatOptions, Context as ChatContext, ModelId, Provider, ResultStream,
 };
-use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
 use reqwest::{Client, Url};
 use reqwest_eventsource::{Event, RequestBuilderExt};
 use tokio_stream::StreamExt;
 use tracing::{debug, info};
 
 use super::model::{ListModelResponse, Model};
-use super::request::Request;
+use super::request::{Request, ResponseFormat};
 use super::response::Response;
 use crate::error::Error;
 use crate::forge_provider::transformers::{ProviderPipeline, Transformer};
-use crate::utils::{format_http_context, sanitize_headers};
+use crate::streaming_timeout::with_token_timeouts;
+use crate::utils::{
+    extract_request_id, format_http_context, headers_to_json, join_base_url,
+    normalize_stop_sequences, sanitize_headers, serialize_with_size_guard, with_request_id_context,
+};
+
+/// OpenAI's documented limit on how many entries `stop` may contain.
+const MAX_STOP_SEQUENCES: usize = 4;
+
+/// Safety cap on `/models` pages followed for a single `models()` call, so a
+/// gateway that never sets `has_more: false` can't paginate forever.
+const MAX_MODEL_PAGES: usize = 20;
 
 #[derive(Clone, Builder)]
 pub struct ForgeProvider {
     client: Client,
     provider: Provider,
     version: String,
+    /// How long to wait for the first streamed chunk after the request is
+    /// sent, before failing with [`crate::error::ProviderError::FirstTokenTimeout`].
+    first_token_timeout: Duration,
+    /// How long to wait for each subsequent chunk once the first has
+    /// arrived, reset on every chunk, before failing with
+    /// [`crate::error::ProviderError::InterTokenTimeout`].
+    inter_token_timeout: Duration,
+    /// Maximum size, in bytes, of a serialized chat request body. `None`
+    /// (the default) disables the check. See
+    /// [`crate::error::ProviderError::RequestTooLarge`].
+    max_request_bytes: Option<u64>,
+    /// Substrings that mark a streamed `200 OK` event body as an in-band
+    /// error from a buggy gateway, converted into a retryable
+    /// [`crate::error::ProviderError::InBandError`] instead of being parsed
+    /// as ordinary content. Empty by default (the check is a no-op). See
+    /// [`forge_app::domain::RetryConfig::retry_on_body_patterns`].
+    #[builder(default)]
+    retry_on_body_patterns: Vec<String>,
 }
 
 impl ForgeProvider {
@@ -29,21 +60,7 @@ impl ForgeProvider {
     }
 
     fn url(&self, path: &str) -> anyhow::Result<Url> {
-        // Validate the path doesn't contain certain patterns
-        if path.contains("://") || path.contains("..") {
-            anyhow::bail!("Invalid path: Contains forbidden patterns");
-        }
-
-        // Remove leading slash to avoid double slashes
-        let path = path.trim_start_matches('/');
-
-        self.provider.to_base_url().join(path).with_context(|| {
-            format!(
-                "Failed to append {} to base URL: {}",
-                path,
-                self.provider.to_base_url()
-            )
-        })
+        join_base_url(&self.provider.to_base_url(), path)
     }
 
     // OpenRouter optional headers ref: https://openrouter.ai/docs/api-reference/overview#headers
@@ -68,6 +85,32 @@ impl ForgeProvider {
             );
         }
 
+        // note: mirrors `Anthropic::headers` — invalid names/values are simply
+        // skipped rather than failing the request, since `Provider` has no
+        // builder-time validation step like `AnthropicBuilder` does.
+        if let Some(extra_headers) = self.provider.extra_headers() {
+            for (name, value) in extra_headers {
+                if let (Ok(name), Ok(value)) =
+                    (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value))
+                {
+                    headers.insert(name, value);
+                }
+            }
+        }
+
+        // Explicit organization/project fields take precedence over anything set
+        // via `extra_headers` for the same header names.
+        if let Some(organization) = self.provider.organization() {
+            if let Ok(value) = HeaderValue::from_str(organization) {
+                headers.insert("OpenAI-Organization", value);
+            }
+        }
+        if let Some(project) = self.provider.project() {
+            if let Ok(value) = HeaderValue::from_str(project) {
+                headers.insert("OpenAI-Project", value);
+            }
+        }
+
         headers.insert("X-Title", HeaderValue::from_static("forge"));
         headers.insert(
             "x-app-version",
@@ -86,14 +129,100 @@ impl ForgeProvider {
         headers
     }
 
+    fn build_request(
+        &self,
+        model: &ModelId,
+        context: ChatContext,
+        options: &ChatOptions,
+    ) -> anyhow::Result<Request> {
+        let mut request = Request::from(context).model(model.clone()).stream(options.stream);
+
+        if let Some(temperature) = options.temperature {
+            request = request.temperature(temperature);
+        }
+        if let Some(top_p) = options.top_p {
+            request = request.top_p(top_p);
+        }
+        if let Some(max_tokens) = options.max_tokens {
+            request = request.max_tokens(max_tokens as u32);
+        }
+        if let Some(stop) = normalize_stop_sequences(options.stop.as_deref(), MAX_STOP_SEQUENCES)?
+        {
+            request = request.stop(stop);
+        }
+        if let Some(seed) = options.seed {
+            request = request.seed(seed as u32);
+        }
+        if let Some(presence_penalty) = options.presence_penalty {
+            request = request.presence_penalty(presence_penalty);
+        }
+        if let Some(frequency_penalty) = options.frequency_penalty {
+            request = request.frequency_penalty(frequency_penalty);
+        }
+        if options.logprobs {
+            request = request.logprobs(true);
+            if let Some(top_logprobs) = options.top_logprobs {
+                request = request.top_logprobs(top_logprobs);
+            }
+        }
+        if let Some(response_format) = &options.response_format {
+            request = request.response_format(ResponseFormat::from(response_format));
+        }
+        if let Some(effort) = options.reasoning_effort.clone() {
+            let mut reasoning = request.reasoning.clone().unwrap_or(
+                forge_app::domain::ReasoningConfig {
+                    enabled: None,
+                    effort: None,
+                    max_tokens: None,
+                    exclude: None,
+                },
+            );
+            reasoning.effort = Some(effort);
+            request = request.reasoning(reasoning);
+        }
+        if let Some(user) = options.user.clone() {
+            request = request.user(user);
+        }
+        if !options.metadata.is_empty() {
+            request = request.metadata(options.metadata.clone());
+        }
+
+        let mut pipeline = ProviderPipeline::new(&self.provider);
+        Ok(pipeline.transform(request))
+    }
+
+    /// Builds the exact JSON body and headers `chat()` would send for
+    /// `model`/`context`, without performing any I/O. Useful for diagnosing
+    /// why a provider rejects a payload, since it reflects the same
+    /// serialization and transformer pipeline `chat()` uses.
+    pub fn build_chat_request(
+        &self,
+        model: &ModelId,
+        context: ChatContext,
+    ) -> anyhow::Result<serde_json::Value> {
+        let request = self.build_request(model, context, &ChatOptions::default())?;
+        let url = self.url("chat/completions")?;
+        let headers = sanitize_headers(&self.headers());
+
+        Ok(serde_json::json!({
+            "url": url.to_string(),
+            "headers": headers_to_json(&headers),
+            "body": request,
+        }))
+    }
+
     async fn inner_chat(
         &self,
         model: &ModelId,
         context: ChatContext,
+        options: &ChatOptions,
     ) -> ResultStream<ChatCompletionMessage, anyhow::Error> {
-        let mut request = Request::from(context).model(model.clone()).stream(true);
-        let mut pipeline = ProviderPipeline::new(&self.provider);
-        request = pipeline.transform(request);
+        let request = self.build_request(model, context, options)?;
+        let body = serialize_with_size_guard(
+            &request,
+            self.max_request_bytes,
+            options.extra_body.as_ref(),
+        )?;
 
         let url = self.url("chat/completions")?;
         let headers = self.headers();
@@ -111,55 +240,92 @@ impl ForgeProvider {
             .client
             .post(url.clone())
             .headers(headers)
-            .json(&request)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
             .eventsource()
             .with_context(|| format_http_context(None, "POST", &url))?;
 
-        let stream = es
-            .take_while(|message| !matches!(message, Err(reqwest_eventsource::Error::StreamEnded)))
-            .then(|event| async {
+        let retry_on_body_patterns = self.retry_on_body_patterns.clone();
+        let stream = with_token_timeouts(
+            Box::pin(es.take_while(|message| {
+                !matches!(message, Err(reqwest_eventsource::Error::StreamEnded))
+            })),
+            self.first_token_timeout,
+            self.inter_token_timeout,
+        )
+        .then(move |event| {
+            let retry_on_body_patterns = retry_on_body_patterns.clone();
+            async move {
                 match event {
-                    Ok(event) => match event {
+                    Err(timeout) => Some(Err(timeout.into())),
+                    Ok(Ok(event)) => match event {
                         Event::Open => None,
-                        Event::Message(event) if ["[DONE]", ""].contains(&event.data.as_str()) => {
+                        // `event.data` is trimmed before this check since the
+                        // SSE spec joins consecutive `data:` lines within one
+                        // event with `\n`, which can leave a trailing newline
+                        // (or leading/trailing spaces from a stray `data:
+                        // [DONE] `) on an otherwise-terminal event.
+                        Event::Message(event) if ["[DONE]", ""].contains(&event.data.trim()) => {
                             debug!("Received completion from Upstream");
                             None
                         }
-                        Event::Message(message) => Some(
-                            serde_json::from_str::<Response>(&message.data)
-                                .with_context(|| {
-                                    format!(
-                                        "Failed to parse Forge Provider response: {}",
-                                        message.data
-                                    )
-                                })
-                                .and_then(|response| {
-                                    ChatCompletionMessage::try_from(response.clone()).with_context(
-                                        || {
+                        // Some gateways signal a mid-stream failure with a
+                        // `200 OK` event whose body contains a recognizable
+                        // error marker instead of a proper status code; treat
+                        // a match as a retryable failure rather than parsing
+                        // it as ordinary content.
+                        Event::Message(message) => {
+                            match retry_on_body_patterns
+                                .iter()
+                                .find(|pattern| message.data.contains(pattern.as_str()))
+                            {
+                                Some(pattern) => Some(Err(crate::error::ProviderError::InBandError {
+                                    pattern: pattern.clone(),
+                                    body: message.data.clone(),
+                                }
+                                .into())),
+                                None => Some(
+                                    serde_json::from_str::<Response>(&message.data)
+                                        .with_context(|| {
                                             format!(
-                                                "Failed to create completion message: {}",
+                                                "Failed to parse Forge Provider response: {}",
                                                 message.data
                                             )
-                                        },
-                                    )
-                                }),
-                        ),
+                                        })
+                                        .and_then(|response| {
+                                            ChatCompletionMessage::try_from(response.clone())
+                                                .with_context(|| {
+                                                    format!(
+                                                        "Failed to create completion message: {}",
+                                                        message.data
+                                                    )
+                                                })
+                                        }),
+                                ),
+                            }
+                        }
                     },
-                    Err(error) => match error {
+                    Ok(Err(error)) => match error {
                         reqwest_eventsource::Error::StreamEnded => None,
                         reqwest_eventsource::Error::InvalidStatusCode(_, response) => {
                             let status = response.status();
+                            let request_id = extract_request_id(response.headers());
                             let body = response.text().await.ok();
-                            Some(Err(Error::InvalidStatusCode(status.as_u16())).with_context(
-                                || match body {
-                                    Some(body) => {
-                                        format!("{status} Reason: {body}")
-                                    }
-                                    None => {
-                                        format!("{status} Reason: [Unknown]")
-                                    }
-                                },
-                            ))
+                            let result =
+                                Err(Error::InvalidStatusCode(status.as_u16())).with_context(
+                                    || match body {
+                                        Some(body) => {
+                                            format!(
+                                                "{status} Reason: {}",
+                                                crate::error::describe_error_body(&body)
+                                            )
+                                        }
+                                        None => {
+                                            format!("{status} Reason: [Unknown]")
+                                        }
+                                    },
+                                );
+                            Some(with_request_id_context(result, request_id.as_deref()))
                         }
                         reqwest_eventsource::Error::InvalidContentType(_, ref response) => {
                             let status_code = response.status();
@@ -172,30 +338,121 @@ impl ForgeProvider {
                         }
                     },
                 }
-            })
-            .filter_map(move |response| {
-                response
-                    .map(|result| result.with_context(|| format_http_context(None, "POST", &url)))
-            });
+            }
+        })
+        .filter_map(move |response| {
+            response.map(|result| result.with_context(|| format_http_context(None, "POST", &url)))
+        });
 
         Ok(Box::pin(stream))
     }
 
+    /// Like [`ForgeProvider::inner_chat`], but sends `"stream": false` and
+    /// parses the single JSON completion response instead of consuming an
+    /// SSE stream, wrapping the result in a one-item stream so callers see a
+    /// uniform return type regardless of which mode was requested.
+    async fn inner_chat_once(
+        &self,
+        model: &ModelId,
+        context: ChatContext,
+        options: &ChatOptions,
+    ) -> ResultStream<ChatCompletionMessage, anyhow::Error> {
+        let request = self.build_request(model, context, options)?;
+        let body = serialize_with_size_guard(
+            &request,
+            self.max_request_bytes,
+            options.extra_body.as_ref(),
+        )?;
+
+        let url = self.url("chat/completions")?;
+        let headers = self.headers();
+
+        info!(
+            url = %url,
+            model = %model,
+            headers = ?sanitize_headers(&headers),
+            "Connecting Upstream (non-streaming)"
+        );
+
+        let response = self
+            .client
+            .post(url.clone())
+            .headers(headers)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await
+            .with_context(|| format_http_context(None, "POST", &url))?;
+
+        let status = response.status();
+        let request_id = extract_request_id(response.headers());
+        let body = response
+            .text()
+            .await
+            .with_context(|| format_http_context(Some(status), "POST", &url))
+            .with_context(|| "Failed to decode response into text")?;
+
+        let message = if status.is_success() {
+            serde_json::from_str::<Response>(&body)
+                .with_context(|| format!("Failed to parse Forge Provider response: {body}"))
+                .and_then(|response| {
+                    ChatCompletionMessage::try_from(response).with_context(|| {
+                        format!("Failed to create completion message: {body}")
+                    })
+                })
+                .map(|message| message.request_id_opt(request_id.clone()))
+        } else {
+            Err(Error::InvalidStatusCode(status.as_u16())).with_context(|| {
+                format!("{status} Reason: {}", crate::error::describe_error_body(&body))
+            })
+        };
+        let message = with_request_id_context(message, request_id.as_deref())
+            .with_context(|| format_http_context(None, "POST", &url));
+
+        Ok(Box::pin(tokio_stream::once(message)))
+    }
+
     async fn inner_models(&self) -> Result<Vec<forge_app::domain::Model>> {
-        let url = self.url("models")?;
-        debug!(url = %url, "Fetching models");
-        match self.fetch_models(url.clone()).await {
-            Err(error) => {
-                tracing::error!(error = ?error, "Failed to fetch models");
-                anyhow::bail!(error)
+        let mut models = Vec::new();
+        let mut after: Option<ModelId> = None;
+
+        for page in 0..MAX_MODEL_PAGES {
+            let mut url = self.url("models")?;
+            if let Some(after) = &after {
+                url.query_pairs_mut().append_pair("after", after.as_str());
             }
-            Ok(response) => {
-                let data: ListModelResponse = serde_json::from_str(&response)
-                    .with_context(|| format_http_context(None, "GET", &url))
-                    .with_context(|| "Failed to deserialize models response")?;
-                Ok(data.data.into_iter().map(Into::into).collect())
+            debug!(url = %url, page, "Fetching models");
+            let response = match self.fetch_models(url.clone()).await {
+                Err(error) => {
+                    tracing::error!(error = ?error, "Failed to fetch models");
+                    anyhow::bail!(error)
+                }
+                Ok(response) => response,
+            };
+            let data: ListModelResponse = serde_json::from_str(&response)
+                .with_context(|| format_http_context(None, "GET", &url))
+                .with_context(|| "Failed to deserialize models response")?;
+
+            let has_more = data.has_more;
+            let last_id = data.data.last().map(|model| model.id.clone());
+            models.extend(data.data.into_iter().map(Into::into));
+
+            if !has_more {
+                return Ok(models);
             }
+            let Some(last_id) = last_id else {
+                // `has_more: true` with an empty page has no cursor to
+                // follow; stop instead of re-fetching the same page forever.
+                return Ok(models);
+            };
+            after = Some(last_id);
         }
+
+        tracing::warn!(
+            pages = MAX_MODEL_PAGES,
+            "stopped paginating /models after reaching the safety cap; the model list may be incomplete"
+        );
+        Ok(models)
     }
 
     async fn fetch_models(&self, url: Url) -> Result<String, anyhow::Error> {
@@ -204,20 +461,35 @@ impl ForgeProvider {
         match self.client.get(url.clone()).headers(headers).send().await {
             Ok(response) => {
                 let status = response.status();
+                let request_id = extract_request_id(response.headers());
                 let ctx_message = format_http_context(Some(status), "GET", &url);
                 let response = response
                     .text()
                     .await
                     .with_context(|| ctx_message.clone())
                     .with_context(|| "Failed to decode response into text")?;
-                if status.is_success() {
+                let result = if status.is_success() {
                     Ok(response)
+                } else if status.as_u16() == 404 {
+                    // A 404 here means this provider doesn't implement a
+                    // `/models` endpoint at all, as opposed to having
+                    // rejected the request; surface it as a typed error so
+                    // `Client::fetch_and_cache_models` can fall back to a
+                    // `with_static_models` set instead of failing outright.
+                    Err(crate::error::ProviderError::from_status(
+                        404,
+                        crate::error::describe_error_body(&response),
+                        None,
+                    ))
+                    .with_context(|| ctx_message)
+                    .with_context(|| "Failed to fetch the models")
                 } else {
                     // treat non 200 response as error.
-                    Err(anyhow::anyhow!(response))
+                    Err(anyhow::anyhow!(crate::error::describe_error_body(&response)))
                         .with_context(|| ctx_message)
                         .with_context(|| "Failed to fetch the models")
-                }
+                };
+                with_request_id_context(result, request_id.as_deref())
             }
             Err(err) => {
                 let ctx_msg = format_http_context(err.status(), "GET", &url);
@@ -235,21 +507,181 @@ impl ForgeProvider {
         model: &ModelId,
         context: ChatContext,
     ) -> ResultStream<ChatCompletionMessage, anyhow::Error> {
-        self.inner_chat(model, context).await
+        self.chat_with_options(model, context, ChatOptions::default()).await
+    }
+
+    pub async fn chat_with_options(
+        &self,
+        model: &ModelId,
+        context: ChatContext,
+        options: ChatOptions,
+    ) -> ResultStream<ChatCompletionMessage, anyhow::Error> {
+        if options.stream {
+            self.inner_chat(model, context, &options).await
+        } else {
+            self.inner_chat_once(model, context, &options).await
+        }
     }
 
     pub async fn models(&self) -> Result<Vec<forge_app::domain::Model>> {
         self.inner_models().await
     }
+
+    /// Requests a fill-in-the-middle completion from Mistral's Codestral
+    /// endpoint (`/fim/completions`), which completes `prompt` given the code
+    /// that follows it as `suffix`. Only meaningful for
+    /// [`forge_app::domain::Provider::mistral`]; other providers don't
+    /// implement this endpoint and will simply fail with a 404.
+    pub async fn fim_completion(
+        &self,
+        model: &ModelId,
+        prompt: String,
+        suffix: Option<String>,
+    ) -> Result<ChatCompletionMessage> {
+        let url = self.url("fim/completions")?;
+        let headers = self.headers();
+        let mut request = Request::default()
+            .model(model.clone())
+            .prompt(prompt)
+            .stream(false);
+        if let Some(suffix) = suffix {
+            request = request.suffix(suffix);
+        }
+
+        info!(url = %url, model = %model, "Requesting FIM completion");
+
+        let response = self
+            .client
+            .post(url.clone())
+            .headers(headers)
+            .json(&request)
+            .send()
+            .await
+            .with_context(|| format_http_context(None, "POST", &url))?;
+
+        let status = response.status();
+        let request_id = extract_request_id(response.headers());
+        let body = response
+            .text()
+            .await
+            .with_context(|| format_http_context(Some(status), "POST", &url))
+            .with_context(|| "Failed to decode response into text")?;
+
+        let message = if status.is_success() {
+            serde_json::from_str::<Response>(&body)
+                .with_context(|| format!("Failed to parse Forge Provider response: {body}"))
+                .and_then(|response| {
+                    ChatCompletionMessage::try_from(response).with_context(|| {
+                        format!("Failed to create completion message: {body}")
+                    })
+                })
+                .map(|message| message.request_id_opt(request_id.clone()))
+        } else {
+            Err(Error::InvalidStatusCode(status.as_u16())).with_context(|| {
+                format!("{status} Reason: {}", crate::error::describe_error_body(&body))
+            })
+        };
+
+        with_request_id_context(message, request_id.as_deref())
+            .with_context(|| format_http_context(None, "POST", &url))
+    }
+
+    pub async fn embeddings(&self, model: &ModelId, inputs: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        let url = self.url("embeddings")?;
+        let headers = self.headers();
+        let request = EmbeddingsRequest { model: model.clone(), input: inputs.clone() };
+
+        info!(url = %url, model = %model, input_count = inputs.len(), "Requesting embeddings");
+
+        let response = self
+            .client
+            .post(url.clone())
+            .headers(headers)
+            .json(&request)
+            .send()
+            .await
+            .with_context(|| format_http_context(None, "POST", &url))
+            .with_context(|| "Failed to fetch embeddings")?;
+
+        let status = response.status();
+        let ctx_msg = format_http_context(Some(status), "POST", &url);
+        let request_id = extract_request_id(response.headers());
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(crate::retry::parse_retry_after);
+        let text = response
+            .text()
+            .await
+            .with_context(|| ctx_msg.clone())
+            .with_context(|| "Failed to decode response into text")?;
+
+        if !status.is_success() {
+            let result = Err(crate::error::ProviderError::from_status(
+                status.as_u16(),
+                crate::error::describe_error_body(&text),
+                retry_after,
+            ))
+            .with_context(|| ctx_msg)
+            .with_context(|| "Failed to fetch embeddings");
+            return with_request_id_context(result, request_id.as_deref());
+        }
+
+        let response: EmbeddingsResponse = serde_json::from_str(&text)
+            .with_context(|| ctx_msg)
+            .with_context(|| "Failed to deserialize embeddings response")?;
+
+        let embeddings: Vec<Vec<f32>> = response
+            .data
+            .into_iter()
+            .map(|entry| entry.embedding)
+            .collect();
+
+        if embeddings.len() != inputs.len() {
+            anyhow::bail!(
+                "Expected {} embeddings, but provider returned {}",
+                inputs.len(),
+                embeddings.len()
+            );
+        }
+
+        Ok(embeddings)
+    }
+}
+
+#[derive(Debug, serde::Serialize)]
+struct EmbeddingsRequest {
+    model: ModelId,
+    input: Vec<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct EmbeddingsResponse {
+    data: Vec<EmbeddingsResponseEntry>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct EmbeddingsResponseEntry {
+    embedding: Vec<f32>,
 }
 
 impl From<Model> for forge_app::domain::Model {
     fn from(value: Model) -> Self {
+        // Mistral's `capabilities` object takes precedence over the
+        // OpenRouter-style `supported_parameters` list when present, since
+        // Mistral's `/models` response doesn't populate the latter at all.
         let tools_supported = value
-            .supported_parameters
-            .iter()
-            .flatten()
-            .any(|param| param == "tools");
+            .capabilities
+            .as_ref()
+            .and_then(|capabilities| capabilities.function_calling)
+            .unwrap_or_else(|| {
+                value
+                    .supported_parameters
+                    .iter()
+                    .flatten()
+                    .any(|param| param == "tools")
+            });
         let supports_parallel_tool_calls = value
             .supported_parameters
             .iter()
@@ -260,15 +692,32 @@ impl From<Model> for forge_app::domain::Model {
             .iter()
             .flatten()
             .any(|param| param == "reasoning");
+        let supports_vision = value
+            .capabilities
+            .as_ref()
+            .and_then(|capabilities| capabilities.vision)
+            .or_else(|| {
+                value
+                    .architecture
+                    .as_ref()
+                    .map(|architecture| architecture.modality.contains("image"))
+            });
 
         forge_app::domain::Model {
             id: value.id,
             name: value.name,
             description: value.description,
-            context_length: value.context_length,
+            context_length: value.context_length.or(value.max_context_length),
             tools_supported: Some(tools_supported),
             supports_parallel_tool_calls: Some(supports_parallel_tool_calls),
             supports_reasoning: Some(is_reasoning_supported),
+            supports_vision,
+            deprecated: value.deprecated.map(|deprecated| {
+                forge_app::domain::DeprecationInfo {
+                    sunset_date: deprecated.sunset_date,
+                    replacement: deprecated.replacement,
+                }
+            }),
         }
     }
 }
@@ -276,9 +725,11 @@ impl From<Model> for forge_app::domain::Model {
 #[cfg(test)]
 mod tests {
     use anyhow::Context;
+    use forge_app::domain::ContextMessage;
     use reqwest::Client;
 
     use super::*;
+    use crate::error::ProviderError;
     use crate::mock_server::{normalize_ports, MockServer};
 
     fn create_provider(base_url: &str) -> anyhow::Result<ForgeProvider> {
@@ -286,12 +737,16 @@ mod tests {
             url: reqwest::Url::parse(base_url)?,
             key: Some("test-api-key".to_string()),
             extra_headers: None,
+            organization: None,
+            project: None,
         };
 
         Ok(ForgeProvider::builder()
             .client(Client::new())
             .provider(provider)
             .version("1.0.0".to_string())
+            .first_token_timeout(Duration::from_secs(30))
+            .inter_token_timeout(Duration::from_secs(300))
             .build()
             .unwrap())
     }
@@ -362,6 +817,26 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_fetch_models_not_found_returns_typed_upstream_error() -> anyhow::Result<()> {
+        let mut fixture = MockServer::new().await;
+        let mock = fixture
+            .mock_models(create_error_response("Not Found", 404), 404)
+            .await;
+
+        let provider = create_provider(&fixture.url())?;
+        let actual = provider.models().await;
+
+        mock.assert_async().await;
+
+        let err = actual.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<crate::error::ProviderError>(),
+            Some(crate::error::ProviderError::Upstream { status: 404, .. })
+        ));
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_fetch_models_server_error() -> anyhow::Result<()> {
         let mut fixture = MockServer::new().await;
@@ -393,6 +868,118 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_fetch_models_populates_capabilities_from_response() -> anyhow::Result<()> {
+        let mut fixture = MockServer::new().await;
+        let response = serde_json::json!({
+            "data": [
+                {
+                    "id": "vision-model",
+                    "name": "Vision Model",
+                    "context_length": 128000,
+                    "architecture": {
+                        "modality": "text+image->text",
+                        "tokenizer": "cl100k"
+                    },
+                    "supported_parameters": ["tools", "supports_parallel_tool_calls", "reasoning"]
+                },
+                {
+                    "id": "text-only-model",
+                    "name": "Text Only Model",
+                    "context_length": 8192,
+                    "architecture": {
+                        "modality": "text->text",
+                        "tokenizer": "cl100k"
+                    },
+                    "supported_parameters": []
+                }
+            ]
+        });
+        let mock = fixture.mock_models(response, 200).await;
+
+        let provider = create_provider(&fixture.url())?;
+        let actual = provider.models().await?;
+
+        mock.assert_async().await;
+
+        let vision_model = actual.iter().find(|m| m.id == ModelId::new("vision-model")).unwrap();
+        assert_eq!(vision_model.context_length, Some(128000));
+        assert_eq!(vision_model.tools_supported, Some(true));
+        assert_eq!(vision_model.supports_parallel_tool_calls, Some(true));
+        assert_eq!(vision_model.supports_reasoning, Some(true));
+        assert_eq!(vision_model.supports_vision, Some(true));
+
+        let text_model = actual.iter().find(|m| m.id == ModelId::new("text-only-model")).unwrap();
+        assert_eq!(text_model.tools_supported, Some(false));
+        assert_eq!(text_model.supports_vision, Some(false));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fetch_models_follows_pagination_cursor_across_two_pages() -> anyhow::Result<()> {
+        let mut fixture = MockServer::new().await;
+        let first_page = serde_json::json!({
+            "data": [{"id": "model-a"}, {"id": "model-b"}],
+            "has_more": true
+        });
+        let second_page = serde_json::json!({
+            "data": [{"id": "model-c"}],
+            "has_more": false
+        });
+        let first_mock = fixture.mock_models_first_page(first_page, 200).await;
+        let second_mock = fixture.mock_models_page("model-b", second_page, 200).await;
+
+        let provider = create_provider(&fixture.url())?;
+        let actual = provider.models().await?;
+
+        first_mock.assert_async().await;
+        second_mock.assert_async().await;
+        assert_eq!(actual.len(), 3);
+        assert!(actual.iter().any(|m| m.id == ModelId::new("model-a")));
+        assert!(actual.iter().any(|m| m.id == ModelId::new("model-b")));
+        assert!(actual.iter().any(|m| m.id == ModelId::new("model-c")));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fetch_models_parses_deprecation_metadata() -> anyhow::Result<()> {
+        let mut fixture = MockServer::new().await;
+        let response = serde_json::json!({
+            "data": [
+                {
+                    "id": "old-model",
+                    "name": "Old Model",
+                    "deprecated": {
+                        "sunset_date": "2025-06-30",
+                        "replacement": "new-model"
+                    }
+                },
+                {
+                    "id": "current-model",
+                    "name": "Current Model"
+                }
+            ]
+        });
+        let mock = fixture.mock_models(response, 200).await;
+
+        let provider = create_provider(&fixture.url())?;
+        let actual = provider.models().await?;
+
+        mock.assert_async().await;
+
+        let old_model = actual.iter().find(|m| m.id == ModelId::new("old-model")).unwrap();
+        let deprecation = old_model.deprecated.as_ref().unwrap();
+        assert_eq!(deprecation.sunset_date.as_deref(), Some("2025-06-30"));
+        assert_eq!(deprecation.replacement.as_deref(), Some("new-model"));
+
+        let current_model =
+            actual.iter().find(|m| m.id == ModelId::new("current-model")).unwrap();
+        assert!(current_model.deprecated.is_none());
+
+        Ok(())
+    }
+
     #[test]
     fn test_copilot_headers_include_integration_id() -> anyhow::Result<()> {
         let mut headers = std::collections::HashMap::new();
@@ -404,12 +991,16 @@ mod tests {
             url: reqwest::Url::parse("https://api.githubcopilot.com/")?,
             key: Some("test-copilot-key".to_string()),
             extra_headers: Some(headers),
+            organization: None,
+            project: None,
         };
 
         let forge_provider = ForgeProvider::builder()
             .client(Client::new())
             .provider(provider)
             .version("1.0.0".to_string())
+            .first_token_timeout(Duration::from_secs(30))
+            .inter_token_timeout(Duration::from_secs(300))
             .build()
             .unwrap();
 
@@ -442,12 +1033,16 @@ mod tests {
             url: reqwest::Url::parse("https://api.openai.com/v1/")?,
             key: Some("test-openai-key".to_string()),
             extra_headers: None,
+            organization: None,
+            project: None,
         };
 
         let forge_provider = ForgeProvider::builder()
             .client(Client::new())
             .provider(provider)
             .version("1.0.0".to_string())
+            .first_token_timeout(Duration::from_secs(30))
+            .inter_token_timeout(Duration::from_secs(300))
             .build()
             .unwrap();
 
@@ -466,6 +1061,63 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_organization_and_project_headers_sent_when_present() -> anyhow::Result<()> {
+        let provider = Provider::OpenAI {
+            url: reqwest::Url::parse("https://api.openai.com/v1/")?,
+            key: Some("test-openai-key".to_string()),
+            extra_headers: None,
+            organization: Some("org-123".to_string()),
+            project: Some("proj-456".to_string()),
+        };
+
+        let forge_provider = ForgeProvider::builder()
+            .client(Client::new())
+            .provider(provider)
+            .version("1.0.0".to_string())
+            .first_token_timeout(Duration::from_secs(30))
+            .inter_token_timeout(Duration::from_secs(300))
+            .build()
+            .unwrap();
+
+        let headers = forge_provider.headers();
+
+        assert_eq!(
+            headers.get("OpenAI-Organization").unwrap().to_str().unwrap(),
+            "org-123"
+        );
+        assert_eq!(headers.get("OpenAI-Project").unwrap().to_str().unwrap(), "proj-456");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_organization_and_project_headers_omitted_when_absent() -> anyhow::Result<()> {
+        let provider = Provider::OpenAI {
+            url: reqwest::Url::parse("https://api.openai.com/v1/")?,
+            key: Some("test-openai-key".to_string()),
+            extra_headers: None,
+            organization: None,
+            project: None,
+        };
+
+        let forge_provider = ForgeProvider::builder()
+            .client(Client::new())
+            .provider(provider)
+            .version("1.0.0".to_string())
+            .first_token_timeout(Duration::from_secs(30))
+            .inter_token_timeout(Duration::from_secs(300))
+            .build()
+            .unwrap();
+
+        let headers = forge_provider.headers();
+
+        assert!(!headers.contains_key("OpenAI-Organization"));
+        assert!(!headers.contains_key("OpenAI-Project"));
+
+        Ok(())
+    }
+
     #[test]
     fn test_error_deserialization() -> Result<()> {
         let content = serde_json::to_string(&serde_json::json!({
@@ -482,4 +1134,477 @@ mod tests {
         assert!(message.is_err());
         Ok(())
     }
+
+    #[test]
+    fn test_build_request_stream_field_toggles() -> anyhow::Result<()> {
+        let provider = create_provider("https://api.openai.com/v1/")?;
+        let model = ModelId::new("gpt-4");
+
+        let streaming =
+            provider.build_request(&model, ChatContext::default(), &ChatOptions::default())?;
+        let non_streaming = provider.build_request(
+            &model,
+            ChatContext::default(),
+            &ChatOptions::default().stream(false),
+        )?;
+
+        assert_eq!(streaming.stream, Some(true));
+        assert_eq!(non_streaming.stream, Some(false));
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_request_merges_sampling_options() -> anyhow::Result<()> {
+        let provider = create_provider("https://api.openai.com/v1/")?;
+        let model = ModelId::new("gpt-4");
+
+        let options = ChatOptions::default()
+            .temperature(0.5_f32)
+            .top_p(0.9_f32)
+            .max_tokens(1234u64)
+            .stop(vec!["STOP".to_string()])
+            .seed(42u64)
+            .presence_penalty(0.1_f32)
+            .frequency_penalty(0.2_f32);
+        let request = provider.build_request(&model, ChatContext::default(), &options)?;
+
+        assert_eq!(request.temperature, Some(0.5));
+        assert_eq!(request.top_p, Some(0.9));
+        assert_eq!(request.max_tokens, Some(1234));
+        assert_eq!(request.stop, Some(vec!["STOP".to_string()]));
+        assert_eq!(request.seed, Some(42));
+        assert_eq!(request.presence_penalty, Some(0.1));
+        assert_eq!(request.frequency_penalty, Some(0.2));
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_request_treats_an_empty_stop_vec_as_no_stops() -> anyhow::Result<()> {
+        let provider = create_provider("https://api.openai.com/v1/")?;
+        let model = ModelId::new("gpt-4");
+
+        let options = ChatOptions::default().stop(Vec::<String>::new());
+        let request = provider.build_request(&model, ChatContext::default(), &options)?;
+
+        assert_eq!(request.stop, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_request_rejects_more_than_four_stop_sequences() -> anyhow::Result<()> {
+        let provider = create_provider("https://api.openai.com/v1/")?;
+        let model = ModelId::new("gpt-4");
+
+        let options = ChatOptions::default()
+            .stop(vec!["a", "b", "c", "d", "e"].into_iter().map(String::from).collect::<Vec<_>>());
+        let err = provider
+            .build_request(&model, ChatContext::default(), &options)
+            .unwrap_err();
+
+        assert!(err.to_string().contains("Too many stop sequences"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_request_sends_user_and_metadata_as_top_level_fields() -> anyhow::Result<()> {
+        let provider = create_provider("https://api.openai.com/v1/")?;
+        let model = ModelId::new("gpt-4");
+
+        let mut metadata = std::collections::HashMap::new();
+        metadata.insert("tenant".to_string(), "acme".to_string());
+        let options = ChatOptions::default().user("user-123").metadata(metadata.clone());
+        let request = provider.build_request(&model, ChatContext::default(), &options)?;
+
+        assert_eq!(request.user, Some("user-123".to_string()));
+        assert_eq!(request.metadata, Some(metadata));
+        Ok(())
+    }
+
+    #[test]
+    fn test_url_respects_a_trailing_slash_base() -> anyhow::Result<()> {
+        let provider = create_provider("https://api.openai.com/v1/")?;
+        let actual = provider.url("chat/completions")?;
+        assert_eq!(actual.as_str(), "https://api.openai.com/v1/chat/completions");
+        Ok(())
+    }
+
+    #[test]
+    fn test_url_does_not_drop_the_last_segment_of_a_base_without_a_trailing_slash() -> anyhow::Result<()> {
+        let provider = create_provider("https://api.openai.com/v1")?;
+        let actual = provider.url("chat/completions")?;
+        assert_eq!(actual.as_str(), "https://api.openai.com/v1/chat/completions");
+        Ok(())
+    }
+
+    #[test]
+    fn test_url_preserves_a_gateway_custom_path_prefix() -> anyhow::Result<()> {
+        let with_slash = create_provider("https://gateway.example.com/api/openai/v1/")?;
+        let without_slash = create_provider("https://gateway.example.com/api/openai/v1")?;
+
+        assert_eq!(
+            with_slash.url("chat/completions")?.as_str(),
+            "https://gateway.example.com/api/openai/v1/chat/completions"
+        );
+        assert_eq!(
+            without_slash.url("chat/completions")?.as_str(),
+            "https://gateway.example.com/api/openai/v1/chat/completions"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_chat_request_reflects_a_custom_gateway_path_prefix() -> anyhow::Result<()> {
+        let provider = create_provider("https://gateway.example.com/api/openai/v1")?;
+        let request = provider.build_chat_request(&ModelId::new("gpt-4"), ChatContext::default())?;
+
+        assert_eq!(
+            request["url"],
+            "https://gateway.example.com/api/openai/v1/chat/completions"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chat_with_options_non_streaming_parses_single_response() -> anyhow::Result<()> {
+        let mut fixture = MockServer::new().await;
+        let mock = fixture
+            .mock_chat_completions_json(serde_json::json!({
+                "id": "chatcmpl-1",
+                "model": "gpt-4",
+                "created": 0,
+                "choices": [{
+                    "index": 0,
+                    "finish_reason": "stop",
+                    "message": { "role": "assistant", "content": "Hello there" }
+                }],
+                "usage": { "prompt_tokens": 5, "completion_tokens": 2, "total_tokens": 7 }
+            }))
+            .await;
+        let provider = create_provider(&fixture.url())?;
+
+        let mut stream = provider
+            .chat_with_options(
+                &ModelId::new("gpt-4"),
+                ChatContext::default(),
+                ChatOptions::default().stream(false),
+            )
+            .await?;
+
+        let first = stream.next().await.unwrap()?;
+        assert_eq!(first.content.as_ref().map(|c| c.as_str()), Some("Hello there"));
+        assert!(stream.next().await.is_none());
+
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chat_non_streaming_captures_request_id_header() -> anyhow::Result<()> {
+        let mut fixture = MockServer::new().await;
+        let mock = fixture
+            .mock_chat_completions_json_with_header(
+                serde_json::json!({
+                    "id": "chatcmpl-1",
+                    "model": "gpt-4",
+                    "created": 0,
+                    "choices": [{
+                        "index": 0,
+                        "finish_reason": "stop",
+                        "message": { "role": "assistant", "content": "Hello there" }
+                    }],
+                    "usage": { "prompt_tokens": 5, "completion_tokens": 2, "total_tokens": 7 }
+                }),
+                "x-request-id",
+                "req_abc123",
+            )
+            .await;
+        let provider = create_provider(&fixture.url())?;
+
+        let mut stream = provider
+            .chat_with_options(
+                &ModelId::new("gpt-4"),
+                ChatContext::default(),
+                ChatOptions::default().stream(false),
+            )
+            .await?;
+
+        let first = stream.next().await.unwrap()?;
+        assert_eq!(first.request_id, Some("req_abc123".to_string()));
+
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chat_rejects_oversized_request_without_sending() -> anyhow::Result<()> {
+        let fixture = MockServer::new().await;
+        let provider_config = Provider::OpenAI {
+            url: reqwest::Url::parse(&fixture.url())?,
+            key: Some("test-api-key".to_string()),
+            extra_headers: None,
+            organization: None,
+            project: None,
+        };
+        let provider = ForgeProvider::builder()
+            .client(Client::new())
+            .provider(provider_config)
+            .version("1.0.0".to_string())
+            .first_token_timeout(Duration::from_secs(30))
+            .inter_token_timeout(Duration::from_secs(300))
+            .max_request_bytes(Some(64))
+            .build()?;
+
+        let context = ChatContext::default()
+            .add_message(ContextMessage::user("a".repeat(256), None));
+
+        let result = provider
+            .chat_with_options(
+                &ModelId::new("gpt-4"),
+                context,
+                ChatOptions::default().stream(false),
+            )
+            .await;
+
+        let error = result.err().expect("expected oversized request to be rejected");
+        assert!(matches!(
+            error.downcast_ref::<ProviderError>(),
+            Some(ProviderError::RequestTooLarge { .. })
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chat_allows_request_under_limit() -> anyhow::Result<()> {
+        let mut fixture = MockServer::new().await;
+        let mock = fixture
+            .mock_chat_completions_json(serde_json::json!({
+                "id": "chatcmpl-1",
+                "model": "gpt-4",
+                "created": 0,
+                "choices": [{
+                    "index": 0,
+                    "finish_reason": "stop",
+                    "message": { "role": "assistant", "content": "Hello there" }
+                }],
+                "usage": { "prompt_tokens": 5, "completion_tokens": 2, "total_tokens": 7 }
+            }))
+            .await;
+        let provider_config = Provider::OpenAI {
+            url: reqwest::Url::parse(&fixture.url())?,
+            key: Some("test-api-key".to_string()),
+            extra_headers: None,
+            organization: None,
+            project: None,
+        };
+        let provider = ForgeProvider::builder()
+            .client(Client::new())
+            .provider(provider_config)
+            .version("1.0.0".to_string())
+            .first_token_timeout(Duration::from_secs(30))
+            .inter_token_timeout(Duration::from_secs(300))
+            .max_request_bytes(Some(1024 * 1024))
+            .build()?;
+
+        let mut stream = provider
+            .chat_with_options(
+                &ModelId::new("gpt-4"),
+                ChatContext::default(),
+                ChatOptions::default().stream(false),
+            )
+            .await?;
+
+        let first = stream.next().await.unwrap()?;
+        assert_eq!(first.content.as_ref().map(|c| c.as_str()), Some("Hello there"));
+
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fim_completion_targets_fim_endpoint() -> anyhow::Result<()> {
+        let mut fixture = MockServer::new().await;
+        let mock = fixture
+            .mock_fim_completions_json(serde_json::json!({
+                "id": "cmpl-1",
+                "model": "codestral-latest",
+                "created": 0,
+                "choices": [{
+                    "index": 0,
+                    "finish_reason": "stop",
+                    "text": "fn main() {}"
+                }],
+                "usage": { "prompt_tokens": 5, "completion_tokens": 4, "total_tokens": 9 }
+            }))
+            .await;
+        let provider = create_provider(&fixture.url())?;
+
+        let actual = provider
+            .fim_completion(
+                &ModelId::new("codestral-latest"),
+                "fn main() {".to_string(),
+                Some("}".to_string()),
+            )
+            .await?;
+
+        mock.assert_async().await;
+        assert_eq!(actual.content.as_ref().map(|c| c.as_str()), Some("fn main() {}"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fetch_models_parses_mistral_capabilities_shape() -> anyhow::Result<()> {
+        let mut fixture = MockServer::new().await;
+        let response = serde_json::json!({
+            "data": [
+                {
+                    "id": "codestral-latest",
+                    "name": "codestral-latest",
+                    "max_context_length": 32768,
+                    "capabilities": {
+                        "completion_chat": true,
+                        "completion_fim": true,
+                        "function_calling": false,
+                        "fine_tuning": false,
+                        "vision": false
+                    }
+                },
+                {
+                    "id": "mistral-large-latest",
+                    "name": "mistral-large-latest",
+                    "max_context_length": 131072,
+                    "capabilities": {
+                        "completion_chat": true,
+                        "completion_fim": false,
+                        "function_calling": true,
+                        "fine_tuning": false,
+                        "vision": false
+                    }
+                }
+            ]
+        });
+        let mock = fixture.mock_models(response, 200).await;
+
+        let provider = create_provider(&fixture.url())?;
+        let actual = provider.models().await?;
+
+        mock.assert_async().await;
+
+        let codestral = actual.iter().find(|m| m.id == ModelId::new("codestral-latest")).unwrap();
+        assert_eq!(codestral.context_length, Some(32768));
+        assert_eq!(codestral.tools_supported, Some(false));
+
+        let mistral_large = actual
+            .iter()
+            .find(|m| m.id == ModelId::new("mistral-large-latest"))
+            .unwrap();
+        assert_eq!(mistral_large.context_length, Some(131072));
+        assert_eq!(mistral_large.tools_supported, Some(true));
+
+        Ok(())
+    }
+
+    /// Comment/heartbeat lines (`: ...`) are invisible to `reqwest_eventsource`
+    /// - they never surface as `Event::Message` - and a trailing space after
+    /// `[DONE]` exercises the `.trim()` hardening in `inner_chat`'s
+    /// completion check. Asserts exactly the two real completions come
+    /// through, with nothing lost or duplicated around the noise.
+    #[tokio::test]
+    async fn test_chat_streaming_tolerates_comments_heartbeats_and_trailing_whitespace(
+    ) -> anyhow::Result<()> {
+        let mut fixture = MockServer::new().await;
+        let body = concat!(
+            ": keep-alive\n",
+            "\n",
+            "data: {\"id\":\"1\",\"created\":0,\"choices\":[{\"delta\":{\"content\":\"Hel\"}}]}\n",
+            "\n",
+            ": another comment\n",
+            "\n",
+            "data: {\"id\":\"1\",\"created\":0,\"choices\":[{\"delta\":{\"content\":\"lo\"}}]}\n",
+            "\n",
+            "data: [DONE] \n",
+            "\n",
+        );
+        let mock = fixture.mock_chat_completions_raw_stream(body).await;
+        let provider = create_provider(&fixture.url())?;
+
+        let stream = provider
+            .chat_with_options(
+                &ModelId::new("gpt-4"),
+                ChatContext::default(),
+                ChatOptions::default(),
+            )
+            .await?;
+        let messages: Vec<_> = stream.collect::<Vec<_>>().await;
+
+        mock.assert_async().await;
+        assert_eq!(messages.len(), 2);
+        assert_eq!(
+            messages[0].as_ref().unwrap().content.as_ref().map(|c| c.as_str()),
+            Some("Hel")
+        );
+        assert_eq!(
+            messages[1].as_ref().unwrap().content.as_ref().map(|c| c.as_str()),
+            Some("lo")
+        );
+        Ok(())
+    }
+
+    /// A gateway that returns `200 OK` with an in-band error embedded in the
+    /// stream body bypasses status-code-based retry entirely; a configured
+    /// `retry_on_body_patterns` entry should catch it and surface a
+    /// retryable `ProviderError::InBandError` instead of parsing the body as
+    /// ordinary content.
+    #[tokio::test]
+    async fn test_chat_streaming_converts_in_band_error_body_to_retryable_error(
+    ) -> anyhow::Result<()> {
+        let mut fixture = MockServer::new().await;
+        let body = concat!(
+            "data: {\"id\":\"1\",\"created\":0,\"choices\":[{\"delta\":{\"content\":\"Hel\"}}]}\n",
+            "\n",
+            "data: {\"error\": \"upstream timeout\"}\n",
+            "\n",
+            "data: [DONE]\n",
+            "\n",
+        );
+        let mock = fixture.mock_chat_completions_raw_stream(body).await;
+
+        let provider_config = Provider::OpenAI {
+            url: reqwest::Url::parse(&fixture.url())?,
+            key: Some("test-api-key".to_string()),
+            extra_headers: None,
+            organization: None,
+            project: None,
+        };
+        let provider = ForgeProvider::builder()
+            .client(Client::new())
+            .provider(provider_config)
+            .version("1.0.0".to_string())
+            .first_token_timeout(Duration::from_secs(30))
+            .inter_token_timeout(Duration::from_secs(300))
+            .retry_on_body_patterns(vec!["upstream timeout".to_string()])
+            .build()?;
+
+        let stream = provider
+            .chat_with_options(
+                &ModelId::new("gpt-4"),
+                ChatContext::default(),
+                ChatOptions::default(),
+            )
+            .await?;
+        let messages: Vec<_> = stream.collect::<Vec<_>>().await;
+
+        mock.assert_async().await;
+        assert_eq!(messages.len(), 2);
+        assert!(messages[0].is_ok(), "the first, unaffected chunk should still come through");
+
+        let error = messages[1].as_ref().unwrap_err();
+        let provider_error = error.downcast_ref::<ProviderError>();
+        assert!(
+            matches!(provider_error, Some(ProviderError::InBandError { .. })),
+            "expected an InBandError, got {error:?}"
+        );
+        assert!(crate::retry::is_retryable(provider_error.unwrap()));
+
+        Ok(())
+    }
 }
@@ -108,6 +108,38 @@ pub struct Tool {
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct ResponseFormat {
     pub r#type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_schema: Option<JsonSchemaFormat>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct JsonSchemaFormat {
+    pub name: String,
+    pub schema: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
+}
+
+impl From<&forge_app::domain::ChatResponseFormat> for ResponseFormat {
+    fn from(value: &forge_app::domain::ChatResponseFormat) -> Self {
+        use forge_app::domain::ChatResponseFormat;
+        match value {
+            ChatResponseFormat::Text => {
+                ResponseFormat { r#type: "text".to_string(), json_schema: None }
+            }
+            ChatResponseFormat::JsonObject => {
+                ResponseFormat { r#type: "json_object".to_string(), json_schema: None }
+            }
+            ChatResponseFormat::JsonSchema(schema) => ResponseFormat {
+                r#type: "json_schema".to_string(),
+                json_schema: Some(JsonSchemaFormat {
+                    name: "response".to_string(),
+                    schema: schema.clone(),
+                    strict: Some(true),
+                }),
+            },
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -128,6 +160,10 @@ pub struct Request {
     pub messages: Option<Vec<Message>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prompt: Option<String>,
+    /// Text to continue *after* the completion, used by Mistral's Codestral
+    /// fill-in-the-middle endpoint. Unused by chat completions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub model: Option<ModelId>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -159,6 +195,8 @@ pub struct Request {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub logit_bias: Option<std::collections::HashMap<u32, f32>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub top_logprobs: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub min_p: Option<f32>,
@@ -184,6 +222,19 @@ pub struct Request {
     pub reasoning: Option<forge_app::domain::ReasoningConfig>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_completion_tokens: Option<u32>,
+    /// OpenAI's native reasoning-effort field for its `o`-series models,
+    /// sent as a plain string rather than nested inside `reasoning` (which
+    /// those models don't accept).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<forge_app::domain::Effort>,
+    /// Stable end-user identifier, forwarded as-is for usage attribution
+    /// and abuse monitoring on OpenAI's side.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Free-form key/value tags for usage attribution. See
+    /// [`forge_app::domain::ChatOptions::metadata`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<std::collections::HashMap<String, String>>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
@@ -258,6 +309,7 @@ impl From<Context> for Request {
             },
             model: None,
             prompt: Default::default(),
+            suffix: Default::default(),
             response_format: Default::default(),
             stop: Default::default(),
             stream: Default::default(),
@@ -271,6 +323,7 @@ impl From<Context> for Request {
             presence_penalty: Default::default(),
             repetition_penalty: Default::default(),
             logit_bias: Default::default(),
+            logprobs: Default::default(),
             top_logprobs: Default::default(),
             min_p: Default::default(),
             top_a: Default::default(),
@@ -284,6 +337,9 @@ impl From<Context> for Request {
             session_id: context.conversation_id.map(|id| id.to_string()),
             reasoning: context.reasoning,
             max_completion_tokens: Default::default(),
+            reasoning_effort: Default::default(),
+            user: Default::default(),
+            metadata: Default::default(),
         }
     }
 }
@@ -468,6 +524,14 @@ mod tests {
         assert_json_snapshot!(router_message);
     }
 
+    #[test]
+    fn test_image_message_conversion() {
+        let image = forge_app::domain::Image::new_base64("aGVsbG8=".to_string(), "image/png");
+        let image_message = ContextMessage::Image(image);
+        let router_message = Message::from(image_message);
+        assert_json_snapshot!(router_message);
+    }
+
     #[test]
     fn test_tool_message_conversion() {
         let tool_result = ToolResult::new(ToolName::new("test_tool"))
@@ -523,4 +587,28 @@ mod tests {
             "\"middle-out\""
         );
     }
+
+    #[test]
+    fn test_response_format_text_serialization() {
+        let format = ResponseFormat::from(&forge_app::domain::ChatResponseFormat::Text);
+        assert_json_snapshot!(format);
+    }
+
+    #[test]
+    fn test_response_format_json_object_serialization() {
+        let format = ResponseFormat::from(&forge_app::domain::ChatResponseFormat::JsonObject);
+        assert_json_snapshot!(format);
+    }
+
+    #[test]
+    fn test_response_format_json_schema_serialization() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "answer": { "type": "string" } },
+            "required": ["answer"],
+        });
+        let format =
+            ResponseFormat::from(&forge_app::domain::ChatResponseFormat::JsonSchema(schema));
+        assert_json_snapshot!(format);
+    }
 }
@@ -36,6 +36,7 @@ pub struct ResponseUsage {
     pub total_tokens: usize,
     pub cost: Option<f64>,
     pub prompt_tokens_details: Option<PromptTokenDetails>,
+    pub completion_tokens_details: Option<CompletionTokenDetails>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -43,6 +44,12 @@ pub struct PromptTokenDetails {
     pub cached_tokens: usize,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CompletionTokenDetails {
+    #[serde(default)]
+    pub reasoning_tokens: usize,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct PromptFilterResult {
     pub content_filter_results: Option<ContentFilterResults>,
@@ -72,19 +79,64 @@ pub enum Choice {
         error: Option<ErrorResponse>,
     },
     NonStreaming {
-        logprobs: Option<serde_json::Value>,
+        logprobs: Option<ResponseLogprobs>,
         index: u32,
         finish_reason: Option<String>,
         message: ResponseMessage,
         error: Option<ErrorResponse>,
     },
     Streaming {
+        #[serde(default)]
+        logprobs: Option<ResponseLogprobs>,
         finish_reason: Option<String>,
         delta: ResponseMessage,
         error: Option<ErrorResponse>,
     },
 }
 
+/// Per-token log-probabilities attached to a [`Choice`] when the request set
+/// `logprobs: true`. See [`forge_app::domain::ChatOptions::logprobs`].
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ResponseLogprobs {
+    pub content: Option<Vec<ResponseTokenLogprob>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ResponseTokenLogprob {
+    pub token: String,
+    pub logprob: f64,
+    #[serde(default)]
+    pub top_logprobs: Vec<ResponseTopLogprob>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ResponseTopLogprob {
+    pub token: String,
+    pub logprob: f64,
+}
+
+impl From<ResponseTokenLogprob> for forge_app::domain::TokenLogprob {
+    fn from(value: ResponseTokenLogprob) -> Self {
+        forge_app::domain::TokenLogprob {
+            token: value.token,
+            logprob: value.logprob,
+            top_logprobs: value.top_logprobs.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<ResponseTopLogprob> for forge_app::domain::TopLogprob {
+    fn from(value: ResponseTopLogprob) -> Self {
+        forge_app::domain::TopLogprob { token: value.token, logprob: value.logprob }
+    }
+}
+
+impl From<ResponseLogprobs> for Vec<forge_app::domain::TokenLogprob> {
+    fn from(value: ResponseLogprobs) -> Self {
+        value.content.into_iter().flatten().map(Into::into).collect()
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct ResponseMessage {
     pub content: Option<String>,
@@ -119,6 +171,11 @@ pub struct ToolCall {
     pub id: Option<ToolCallId>,
     pub r#type: FunctionType,
     pub function: FunctionCall,
+    /// Present on streaming deltas; identifies which tool call a fragment
+    /// belongs to when multiple tool calls stream interleaved with one
+    /// another.
+    #[serde(default)]
+    pub index: Option<usize>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -139,6 +196,10 @@ impl From<ResponseUsage> for Usage {
                 .prompt_tokens_details
                 .map(|token_details| token_details.cached_tokens)
                 .unwrap_or_default(),
+            reasoning_tokens: usage
+                .completion_tokens_details
+                .map(|token_details| token_details.reasoning_tokens)
+                .unwrap_or_default(),
             cost: usage.cost,
             ..Default::default()
         }
@@ -150,7 +211,14 @@ impl TryFrom<Response> for ChatCompletionMessage {
 
     fn try_from(res: Response) -> Result<Self, Self::Error> {
         match res {
-            Response::Success { choices, usage, prompt_filter_results, .. } => {
+            Response::Success {
+                choices,
+                usage,
+                prompt_filter_results,
+                provider,
+                system_fingerprint,
+                ..
+            } => {
                 // Handle case where choices is empty (e.g., content filtered by Copilot)
                 if choices.is_empty() {
                     // Check if this is due to content filtering
@@ -184,7 +252,7 @@ impl TryFrom<Response> for ChatCompletionMessage {
                                     .and_then(|s| FinishReason::from_str(&s).ok()),
                             )
                         }
-                        Choice::NonStreaming { message, finish_reason, .. } => {
+                        Choice::NonStreaming { message, finish_reason, logprobs, .. } => {
                             let mut resp = ChatCompletionMessage::assistant(Content::full(
                                 message.content.clone().unwrap_or_default(),
                             ))
@@ -193,6 +261,11 @@ impl TryFrom<Response> for ChatCompletionMessage {
                                     .clone()
                                     .and_then(|s| FinishReason::from_str(&s).ok()),
                             );
+                            if let Some(logprobs) = logprobs.clone() {
+                                resp = resp.logprobs(Vec::<forge_app::domain::TokenLogprob>::from(
+                                    logprobs,
+                                ));
+                            }
                             if let Some(reasoning) = &message.reasoning {
                                 resp = resp.reasoning(Content::full(reasoning.clone()));
                             }
@@ -227,7 +300,7 @@ impl TryFrom<Response> for ChatCompletionMessage {
                             }
                             resp
                         }
-                        Choice::Streaming { delta, finish_reason, .. } => {
+                        Choice::Streaming { delta, finish_reason, logprobs, .. } => {
                             let mut resp = ChatCompletionMessage::assistant(Content::part(
                                 delta.content.clone().unwrap_or_default(),
                             ))
@@ -237,6 +310,12 @@ impl TryFrom<Response> for ChatCompletionMessage {
                                     .and_then(|s| FinishReason::from_str(&s).ok()),
                             );
 
+                            if let Some(logprobs) = logprobs.clone() {
+                                resp = resp.logprobs(Vec::<forge_app::domain::TokenLogprob>::from(
+                                    logprobs,
+                                ));
+                            }
+
                             if let Some(reasoning) = &delta.reasoning {
                                 resp = resp.reasoning(Content::part(reasoning.clone()));
                             }
@@ -259,6 +338,7 @@ impl TryFrom<Response> for ChatCompletionMessage {
                                         call_id: tool_call.id.clone(),
                                         name: tool_call.function.name.clone(),
                                         arguments_part: tool_call.function.arguments.clone(),
+                                        index: tool_call.index,
                                     });
                                 }
                             }
@@ -269,6 +349,8 @@ impl TryFrom<Response> for ChatCompletionMessage {
                     if let Some(usage) = usage {
                         response.usage = Some(usage.into());
                     }
+                    response = response.upstream_provider_opt(provider);
+                    response = response.system_fingerprint_opt(system_fingerprint);
                     Ok(response)
                 } else {
                     // This should not happen anymore due to the empty check above
@@ -314,6 +396,36 @@ mod tests {
         assert!(Fixture::test_response_compatibility(event));
     }
 
+    #[test]
+    fn test_forge_response_event_captures_upstream_provider() {
+        let event = "{\"id\":\"gen-1739949430-JZMcABaj4fg8oFDtRNDZ\",\"provider\":\"OpenAI\",\"model\":\"openai/gpt-4o-mini\",\"object\":\"chat.completion.chunk\",\"created\":1739949430,\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"content\":\"hello\",\"refusal\":null},\"logprobs\":null,\"finish_reason\":null,\"native_finish_reason\":null}],\"system_fingerprint\":\"fp_00428b782a\"}";
+
+        let response: Response = serde_json::from_str(event).unwrap();
+        let message = ChatCompletionMessage::try_from(response).unwrap();
+
+        assert_eq!(message.upstream_provider, Some("OpenAI".to_string()));
+    }
+
+    #[test]
+    fn test_open_ai_response_event_captures_system_fingerprint() {
+        let event = "{\"id\":\"chatcmpl-B2YVxGR9TaLBrEcFMVCv2B4IcNe4g\",\"object\":\"chat.completion.chunk\",\"created\":1739949029,\"model\":\"gpt-4o-mini-2024-07-18\",\"service_tier\":\"default\",\"system_fingerprint\":\"fp_00428b782a\",\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"content\":\"hello\",\"refusal\":null},\"logprobs\":null,\"finish_reason\":null}]}";
+
+        let response: Response = serde_json::from_str(event).unwrap();
+        let message = ChatCompletionMessage::try_from(response).unwrap();
+
+        assert_eq!(message.system_fingerprint, Some("fp_00428b782a".to_string()));
+    }
+
+    #[test]
+    fn test_open_ai_response_event_leaves_upstream_provider_none() {
+        let event = "{\"id\":\"chatcmpl-B2YVxGR9TaLBrEcFMVCv2B4IcNe4g\",\"object\":\"chat.completion.chunk\",\"created\":1739949029,\"model\":\"gpt-4o-mini-2024-07-18\",\"service_tier\":\"default\",\"system_fingerprint\":\"fp_00428b782a\",\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"content\":\"hello\",\"refusal\":null},\"logprobs\":null,\"finish_reason\":null}]}";
+
+        let response: Response = serde_json::from_str(event).unwrap();
+        let message = ChatCompletionMessage::try_from(response).unwrap();
+
+        assert_eq!(message.upstream_provider, None);
+    }
+
     #[test]
     fn test_reasoning_response_event() {
         let event = "{\"id\":\"gen-1751626123-nYRpHzdA0thRXF0LoQi0\",\"provider\":\"Google\",\"model\":\"anthropic/claude-3.7-sonnet:thinking\",\"object\":\"chat.completion.chunk\",\"created\":1751626123,\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"content\":\"\",\"reasoning\":\"I need to check\",\"reasoning_details\":[{\"type\":\"reasoning.text\",\"text\":\"I need to check\"}]},\"finish_reason\":null,\"native_finish_reason\":null,\"logprobs\":null}]}";
@@ -338,6 +450,32 @@ mod tests {
         assert!(Fixture::test_response_compatibility(event));
     }
 
+    #[test]
+    fn test_non_streaming_response_surfaces_logprobs() {
+        let event = "{\"id\":\"chatcmpl-1\",\"object\":\"chat.completion\",\"created\":1739949029,\"model\":\"gpt-4o-mini\",\"choices\":[{\"index\":0,\"finish_reason\":\"stop\",\"message\":{\"role\":\"assistant\",\"content\":\"Hi\"},\"logprobs\":{\"content\":[{\"token\":\"Hi\",\"logprob\":-0.31725305,\"bytes\":[72,105],\"top_logprobs\":[{\"token\":\"Hi\",\"logprob\":-0.31725305,\"bytes\":[72,105]},{\"token\":\"Hello\",\"logprob\":-1.9,\"bytes\":null}]}]}}]}";
+
+        let response: Response = serde_json::from_str(event).unwrap();
+        let message = ChatCompletionMessage::try_from(response).unwrap();
+
+        let logprobs = message.logprobs.expect("expected logprobs to be present");
+        assert_eq!(logprobs.len(), 1);
+        assert_eq!(logprobs[0].token, "Hi");
+        assert_eq!(logprobs[0].top_logprobs.len(), 2);
+        assert_eq!(logprobs[0].top_logprobs[1].token, "Hello");
+    }
+
+    #[test]
+    fn test_streaming_response_surfaces_logprobs() {
+        let event = "{\"id\":\"chatcmpl-1\",\"object\":\"chat.completion.chunk\",\"created\":1739949029,\"model\":\"gpt-4o-mini\",\"choices\":[{\"index\":0,\"finish_reason\":null,\"delta\":{\"role\":\"assistant\",\"content\":\"Hi\"},\"logprobs\":{\"content\":[{\"token\":\"Hi\",\"logprob\":-0.31725305,\"top_logprobs\":[]}]}}]}";
+
+        let response: Response = serde_json::from_str(event).unwrap();
+        let message = ChatCompletionMessage::try_from(response).unwrap();
+
+        let logprobs = message.logprobs.expect("expected logprobs to be present");
+        assert_eq!(logprobs[0].token, "Hi");
+        assert_eq!(logprobs[0].logprob, -0.31725305);
+    }
+
     #[test]
     fn test_responses() -> anyhow::Result<()> {
         let input = include_str!("./responses.jsonl").split("\n");
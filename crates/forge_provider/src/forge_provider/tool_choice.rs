@@ -6,6 +6,11 @@ pub enum ToolChoice {
     None,
     Auto,
     Required,
+    /// Mistral's equivalent of `Required`: forces a tool call, but spelled
+    /// `"any"` instead of `"required"`. Never produced from
+    /// `forge_app::domain::ToolChoice` directly; Mistral requests get it via
+    /// `NormalizeToolChoiceForMistral` rewriting `Required` post-pipeline.
+    Any,
     #[serde(untagged)]
     Function {
         r#type: FunctionType,
@@ -68,6 +73,10 @@ mod tests {
         let choice_auto = ToolChoice::Auto;
         assert_eq!(serde_json::to_string(&choice_auto).unwrap(), r#""auto""#);
 
+        // Test Any variant (Mistral's "force a tool call" spelling)
+        let choice_any = ToolChoice::Any;
+        assert_eq!(serde_json::to_string(&choice_any).unwrap(), r#""any""#);
+
         // Test Function variant
         let choice_function = ToolChoice::Function {
             function: FunctionName { name: "test_tool".to_string() },
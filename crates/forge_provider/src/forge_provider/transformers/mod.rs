@@ -1,6 +1,7 @@
 mod drop_tool_call;
 mod make_openai_compat;
 mod pipeline;
+mod reasoning_model;
 mod set_cache;
 mod tool_choice;
 mod when_model;
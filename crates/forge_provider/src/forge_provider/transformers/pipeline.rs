@@ -2,8 +2,9 @@ use forge_app::domain::{DefaultTransformation, Provider, Transformer};
 
 use super::drop_tool_call::DropToolCalls;
 use super::make_openai_compat::MakeOpenAiCompat;
+use super::reasoning_model::ReasoningModelParams;
 use super::set_cache::SetCache;
-use super::tool_choice::SetToolChoice;
+use super::tool_choice::{NormalizeToolChoiceForMistral, SetToolChoice};
 use super::when_model::when_model;
 use crate::forge_provider::request::Request;
 use crate::forge_provider::tool_choice::ToolChoice;
@@ -31,13 +32,26 @@ impl Transformer for ProviderPipeline<'_> {
             .pipe(SetCache.when(when_model("gemini|anthropic")))
             .when(move |_| supports_open_router_params(provider));
 
+        let reasoning_model_params = ReasoningModelParams
+            .when(when_model(REASONING_MODEL_PATTERN))
+            .when(move |_: &Request| !supports_open_router_params(provider));
         let open_ai_compat = MakeOpenAiCompat.when(move |_| !supports_open_router_params(provider));
+        let mistral_tool_choice =
+            NormalizeToolChoiceForMistral.when(move |_| provider.is_mistral());
 
-        let mut combined = or_transformers.pipe(open_ai_compat);
+        let mut combined = or_transformers
+            .pipe(reasoning_model_params)
+            .pipe(open_ai_compat)
+            .pipe(mistral_tool_choice);
         combined.transform(request)
     }
 }
 
+/// Matches OpenAI's `o`-series reasoning model ids (`o1`, `o1-mini`,
+/// `o3-mini`, `o4-mini`, ...), anchored so it doesn't false-positive on
+/// unrelated ids that merely end in `o` (e.g. `gpt-4o`).
+const REASONING_MODEL_PATTERN: &str = r"(^|/)o[134](-|$)";
+
 /// function checks if provider supports open-router parameters.
 fn supports_open_router_params(provider: &Provider) -> bool {
     provider.is_open_router() || provider.is_forge()
@@ -59,6 +73,52 @@ mod tests {
             "requesty"
         )));
         assert!(!supports_open_router_params(&Provider::xai("xai")));
+        assert!(!supports_open_router_params(&Provider::mistral("mistral")));
         assert!(!supports_open_router_params(&Provider::anthropic("claude")));
     }
+
+    #[test]
+    fn test_reasoning_model_drops_temperature_and_uses_reasoning_effort() {
+        use forge_app::domain::{Effort, ModelId, ReasoningConfig};
+
+        let provider = Provider::openai("openai-key");
+        let request = Request::default()
+            .model(ModelId::new("o1-mini"))
+            .temperature(0.7)
+            .max_tokens(100)
+            .reasoning(ReasoningConfig {
+                enabled: None,
+                effort: Some(Effort::High),
+                max_tokens: None,
+                exclude: None,
+            });
+
+        let mut pipeline = ProviderPipeline::new(&provider);
+        let actual = pipeline.transform(request);
+
+        assert_eq!(actual.temperature, None);
+        assert_eq!(actual.reasoning_effort, Some(Effort::High));
+        assert_eq!(actual.reasoning, None);
+        assert_eq!(actual.max_tokens, None);
+        assert_eq!(actual.max_completion_tokens, Some(100));
+    }
+
+    #[test]
+    fn test_standard_model_keeps_temperature_and_has_no_reasoning_effort() {
+        use forge_app::domain::ModelId;
+
+        let provider = Provider::openai("openai-key");
+        let request = Request::default()
+            .model(ModelId::new("gpt-4o"))
+            .temperature(0.7)
+            .max_tokens(100);
+
+        let mut pipeline = ProviderPipeline::new(&provider);
+        let actual = pipeline.transform(request);
+
+        assert_eq!(actual.temperature, Some(0.7));
+        assert_eq!(actual.reasoning_effort, None);
+        assert_eq!(actual.max_tokens, None);
+        assert_eq!(actual.max_completion_tokens, Some(100));
+    }
 }
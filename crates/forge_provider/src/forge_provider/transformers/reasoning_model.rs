@@ -0,0 +1,63 @@
+use forge_app::domain::Transformer;
+
+use crate::forge_provider::request::Request;
+
+/// OpenAI's `o`-series reasoning models (o1, o3, o4-mini, ...) reject
+/// `temperature`/`top_p` outright and expose reasoning effort as their own
+/// top-level `reasoning_effort` string rather than the OpenRouter-style
+/// `reasoning` object `MakeOpenAiCompat` drops for direct OpenAI. Applied
+/// before that drop so the effort survives, translated to the field these
+/// models actually accept.
+pub struct ReasoningModelParams;
+
+impl Transformer for ReasoningModelParams {
+    type Value = Request;
+
+    fn transform(&mut self, mut request: Self::Value) -> Self::Value {
+        request.temperature = None;
+        request.top_p = None;
+        request.reasoning_effort = request
+            .reasoning
+            .as_ref()
+            .and_then(|reasoning| reasoning.effort.clone());
+
+        request
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_app::domain::{Effort, ReasoningConfig};
+
+    use super::*;
+
+    #[test]
+    fn test_drops_temperature_and_top_p() {
+        let fixture = Request::default().temperature(0.7).top_p(0.9);
+        let mut transformer = ReasoningModelParams;
+        let actual = transformer.transform(fixture);
+        assert_eq!(actual.temperature, None);
+        assert_eq!(actual.top_p, None);
+    }
+
+    #[test]
+    fn test_lifts_effort_out_of_reasoning_object() {
+        let fixture = Request::default().reasoning(ReasoningConfig {
+            enabled: None,
+            effort: Some(Effort::High),
+            max_tokens: None,
+            exclude: None,
+        });
+        let mut transformer = ReasoningModelParams;
+        let actual = transformer.transform(fixture);
+        assert_eq!(actual.reasoning_effort, Some(Effort::High));
+    }
+
+    #[test]
+    fn test_no_reasoning_config_leaves_effort_unset() {
+        let fixture = Request::default();
+        let mut transformer = ReasoningModelParams;
+        let actual = transformer.transform(fixture);
+        assert_eq!(actual.reasoning_effort, None);
+    }
+}
@@ -22,12 +22,48 @@ impl Transformer for SetToolChoice {
     }
 }
 
+/// Mistral rejects `"required"` for `tool_choice`, accepting `"any"` instead
+/// to mean the same thing (force a tool call). Everything else
+/// (`none`/`auto`/a specific function) is passed through unchanged.
+pub struct NormalizeToolChoiceForMistral;
+
+impl Transformer for NormalizeToolChoiceForMistral {
+    type Value = Request;
+
+    fn transform(&mut self, mut request: Self::Value) -> Self::Value {
+        if request.tool_choice == Some(ToolChoice::Required) {
+            request.tool_choice = Some(ToolChoice::Any);
+        }
+        request
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use forge_app::domain::{Context, ModelId};
 
     use super::*;
 
+    #[test]
+    fn test_mistral_tool_choice_rewrites_required_to_any() {
+        let request = Request::default().tool_choice(ToolChoice::Required);
+
+        let mut transformer = NormalizeToolChoiceForMistral;
+        let transformed = transformer.transform(request);
+
+        assert_eq!(transformed.tool_choice, Some(ToolChoice::Any));
+    }
+
+    #[test]
+    fn test_mistral_tool_choice_leaves_auto_unchanged() {
+        let request = Request::default().tool_choice(ToolChoice::Auto);
+
+        let mut transformer = NormalizeToolChoiceForMistral;
+        let transformed = transformer.transform(request);
+
+        assert_eq!(transformed.tool_choice, Some(ToolChoice::Auto));
+    }
+
     #[test]
     fn test_gemini_transformer_tool_strategy() {
         let context = Context::default();
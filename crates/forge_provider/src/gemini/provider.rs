@@ -0,0 +1,160 @@
+use anyhow::Context as _;
+use derive_builder::Builder;
+use forge_app::domain::{ChatCompletionMessage, Context, Model, ModelId, ResultStream};
+use reqwest::{Client, Url};
+use reqwest_eventsource::{Event, RequestBuilderExt};
+use tokio_stream::StreamExt;
+use tracing::debug;
+
+use super::request::GenerateContentRequest;
+use super::response::{GenerateContentResponse, ListModelResponse};
+use crate::error::Error;
+use crate::utils::format_http_context;
+
+#[derive(Clone, Builder)]
+pub struct Gemini {
+    client: Client,
+    api_key: String,
+    base_url: Url,
+}
+
+impl Gemini {
+    pub fn builder() -> GeminiBuilder {
+        GeminiBuilder::default()
+    }
+
+    fn url(&self, path: &str) -> anyhow::Result<Url> {
+        if path.contains("://") || path.contains("..") {
+            anyhow::bail!("Invalid path: Contains forbidden patterns");
+        }
+
+        let path = path.trim_start_matches('/');
+
+        self.base_url
+            .join(path)
+            .with_context(|| format!("Failed to append {} to base URL: {}", path, self.base_url))
+    }
+
+    fn chat_url(&self, model: &ModelId) -> anyhow::Result<Url> {
+        self.url(&format!(
+            "models/{}:streamGenerateContent?alt=sse&key={}",
+            model.as_str(),
+            self.api_key
+        ))
+    }
+
+    /// Builds the exact JSON body `chat()` would send for `model`/`context`,
+    /// without performing any I/O. Useful for diagnosing why a provider
+    /// rejects a payload, since it reflects the same serialization `chat()`
+    /// uses. The API key embedded in `chat()`'s query string is redacted.
+    pub fn build_chat_request(
+        &self,
+        model: &ModelId,
+        context: Context,
+    ) -> anyhow::Result<serde_json::Value> {
+        let request = GenerateContentRequest::from(context);
+        let mut url = self.chat_url(model)?;
+        url.query_pairs_mut().clear().append_pair("alt", "sse").append_pair("key", "[REDACTED]");
+
+        Ok(serde_json::json!({
+            "url": url.to_string(),
+            "headers": {},
+            "body": request,
+        }))
+    }
+
+    pub async fn chat(
+        &self,
+        model: &ModelId,
+        context: Context,
+    ) -> ResultStream<ChatCompletionMessage, anyhow::Error> {
+        let request = GenerateContentRequest::from(context);
+        let url = self.chat_url(model)?;
+
+        debug!(url = %url, model = %model, "Connecting Upstream");
+        let es = self
+            .client
+            .post(url.clone())
+            .json(&request)
+            .eventsource()
+            .with_context(|| format_http_context(None, "POST", &url))?;
+
+        let stream = es
+            .take_while(|message| !matches!(message, Err(reqwest_eventsource::Error::StreamEnded)))
+            .then(|event| async {
+                match event {
+                    Ok(Event::Open) => None,
+                    Ok(Event::Message(message)) => Some(
+                        serde_json::from_str::<GenerateContentResponse>(&message.data)
+                            .with_context(|| "Failed to parse Gemini event")
+                            .and_then(ChatCompletionMessage::try_from),
+                    ),
+                    Err(reqwest_eventsource::Error::StreamEnded) => None,
+                    Err(reqwest_eventsource::Error::InvalidStatusCode(_, response)) => {
+                        let status = response.status();
+                        let body = response.text().await.ok();
+                        Some(
+                            Err(Error::InvalidStatusCode(status.as_u16())).with_context(|| {
+                                match body {
+                                    Some(body) => format!("{status} Reason: {body}"),
+                                    None => format!("{status} Reason: [Unknown]"),
+                                }
+                            }),
+                        )
+                    }
+                    Err(error) => {
+                        tracing::error!(error = ?error, "Failed to receive chat completion event");
+                        Some(Err(error.into()))
+                    }
+                }
+            })
+            .map(move |response| match response {
+                Some(Err(err)) => {
+                    Some(Err(err).with_context(|| format_http_context(None, "POST", &url)))
+                }
+                _ => response,
+            });
+
+        Ok(Box::pin(stream.filter_map(|x| x)))
+    }
+
+    pub async fn models(&self) -> anyhow::Result<Vec<Model>> {
+        let url = self.url(&format!("models?key={}", self.api_key))?;
+        debug!(url = %url, "Fetching models");
+
+        let response = self
+            .client
+            .get(url.clone())
+            .send()
+            .await
+            .with_context(|| format_http_context(None, "GET", &url))
+            .with_context(|| "Failed to fetch models")?;
+
+        let status = response.status();
+        let ctx_msg = format_http_context(Some(status), "GET", &url);
+        let text = response
+            .text()
+            .await
+            .with_context(|| ctx_msg.clone())
+            .with_context(|| "Failed to decode response into text")?;
+
+        if status.is_success() {
+            let response: ListModelResponse = serde_json::from_str(&text)
+                .with_context(|| ctx_msg)
+                .with_context(|| "Failed to deserialize models response")?;
+            Ok(response.models.into_iter().map(Into::into).collect())
+        } else {
+            Err(anyhow::anyhow!(text))
+                .with_context(|| ctx_msg)
+                .with_context(|| "Failed to fetch the models")
+        }
+    }
+
+    pub async fn embeddings(
+        &self,
+        _model: &ModelId,
+        _inputs: Vec<String>,
+    ) -> anyhow::Result<Vec<Vec<f32>>> {
+        anyhow::bail!("Gemini embeddings are not yet supported by this client")
+    }
+}
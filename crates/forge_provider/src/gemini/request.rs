@@ -0,0 +1,111 @@
+use forge_app::domain::{Context, ContextMessage, Role, ToolOutput, ToolValue};
+use serde::Serialize;
+
+#[derive(Debug, Serialize, Default)]
+pub struct GenerateContentRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_instruction: Option<SystemInstruction>,
+    pub contents: Vec<GeminiContent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generation_config: Option<GenerationConfig>,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct SystemInstruction {
+    pub parts: Vec<Part>,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct GenerationConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GeminiContent {
+    pub role: String,
+    pub parts: Vec<Part>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Part {
+    Text { text: String },
+    FunctionResponse { function_response: FunctionResponse },
+}
+
+#[derive(Debug, Serialize)]
+pub struct FunctionResponse {
+    pub name: String,
+    pub response: serde_json::Value,
+}
+
+impl From<Context> for GenerateContentRequest {
+    fn from(context: Context) -> Self {
+        let mut system_parts = Vec::new();
+        let mut contents = Vec::new();
+
+        for message in context.messages {
+            match message {
+                ContextMessage::Text(text) if text.role == Role::System => {
+                    system_parts.push(Part::Text { text: text.content });
+                }
+                ContextMessage::Text(text) => {
+                    let role = match text.role {
+                        Role::Assistant => "model",
+                        _ => "user",
+                    };
+                    contents.push(GeminiContent {
+                        role: role.to_string(),
+                        parts: vec![Part::Text { text: text.content }],
+                    });
+                }
+                ContextMessage::Tool(tool_result) => {
+                    contents.push(GeminiContent {
+                        role: "function".to_string(),
+                        parts: vec![Part::FunctionResponse {
+                            function_response: FunctionResponse {
+                                name: tool_result.name.to_string(),
+                                response: serde_json::json!({
+                                    "result": tool_output_to_text(&tool_result.output),
+                                }),
+                            },
+                        }],
+                    });
+                }
+                ContextMessage::Image(_) => {
+                    // Image parts are not yet translated for Gemini.
+                }
+            }
+        }
+
+        let generation_config = GenerationConfig {
+            max_output_tokens: context.max_tokens.map(|v| v as u64),
+            temperature: context.temperature.map(|v| v.value()),
+            top_p: context.top_p.map(|v| v.value()),
+        };
+
+        Self {
+            system_instruction: (!system_parts.is_empty())
+                .then(|| SystemInstruction { parts: system_parts }),
+            contents,
+            generation_config: Some(generation_config),
+        }
+    }
+}
+
+fn tool_output_to_text(output: &ToolOutput) -> String {
+    output
+        .values
+        .iter()
+        .filter_map(|value| match value {
+            ToolValue::Text(text) => Some(text.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,160 @@
+use forge_app::domain::{ChatCompletionMessage, Content, FinishReason, Model, ModelId, Usage};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct GenerateContentResponse {
+    #[serde(default)]
+    pub candidates: Vec<Candidate>,
+    #[serde(default)]
+    pub usage_metadata: Option<UsageMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Candidate {
+    #[serde(default)]
+    pub content: Option<CandidateContent>,
+    #[serde(default)]
+    pub finish_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CandidateContent {
+    #[serde(default)]
+    pub parts: Vec<CandidatePart>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CandidatePart {
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageMetadata {
+    #[serde(default)]
+    pub prompt_token_count: usize,
+    #[serde(default)]
+    pub candidates_token_count: usize,
+    #[serde(default)]
+    pub total_token_count: usize,
+}
+
+impl TryFrom<GenerateContentResponse> for ChatCompletionMessage {
+    type Error = anyhow::Error;
+
+    fn try_from(response: GenerateContentResponse) -> Result<Self, Self::Error> {
+        let candidate = response.candidates.into_iter().next();
+        let text = candidate
+            .as_ref()
+            .and_then(|candidate| candidate.content.as_ref())
+            .map(|content| {
+                content
+                    .parts
+                    .iter()
+                    .filter_map(|part| part.text.clone())
+                    .collect::<Vec<_>>()
+                    .join("")
+            })
+            .unwrap_or_default();
+
+        let finish_reason = candidate
+            .and_then(|candidate| candidate.finish_reason)
+            .and_then(|reason| match reason.as_str() {
+                "STOP" => Some(FinishReason::Stop),
+                "MAX_TOKENS" => Some(FinishReason::Length),
+                "SAFETY" | "RECITATION" => Some(FinishReason::ContentFilter),
+                other => Some(FinishReason::Other(other.to_string())),
+            });
+
+        let usage = response.usage_metadata.map(|usage| Usage {
+            prompt_tokens: usage.prompt_token_count,
+            completion_tokens: usage.candidates_token_count,
+            total_tokens: usage.total_token_count,
+            estimated_tokens: 0,
+            cached_tokens: 0,
+            cache_write_tokens: 0,
+            reasoning_tokens: 0,
+            cost: None,
+        });
+
+        Ok(ChatCompletionMessage {
+            content: Some(Content::part(text)),
+            reasoning: None,
+            reasoning_details: None,
+            tool_calls: vec![],
+            finish_reason,
+            usage,
+            request_id: None,
+            upstream_provider: None,
+            logprobs: None,
+            system_fingerprint: None,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListModelResponse {
+    #[serde(default)]
+    pub models: Vec<GeminiModel>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiModel {
+    pub name: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub input_token_limit: Option<u64>,
+}
+
+impl From<GeminiModel> for Model {
+    fn from(value: GeminiModel) -> Self {
+        let id = value.name.trim_start_matches("models/").to_string();
+        Model {
+            id: ModelId::new(id),
+            name: value.display_name,
+            description: value.description,
+            context_length: value.input_token_limit,
+            tools_supported: None,
+            supports_parallel_tool_calls: None,
+            supports_reasoning: None,
+            supports_vision: None,
+            deprecated: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn response_with_finish_reason(reason: &str) -> GenerateContentResponse {
+        GenerateContentResponse {
+            candidates: vec![Candidate { content: None, finish_reason: Some(reason.to_string()) }],
+            usage_metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_finish_reason_mapping() {
+        let cases = [
+            ("STOP", FinishReason::Stop),
+            ("MAX_TOKENS", FinishReason::Length),
+            ("SAFETY", FinishReason::ContentFilter),
+            ("RECITATION", FinishReason::ContentFilter),
+            ("OTHER", FinishReason::Other("OTHER".to_string())),
+        ];
+
+        for (reason, expected) in cases {
+            let message: ChatCompletionMessage =
+                response_with_finish_reason(reason).try_into().unwrap();
+            assert_eq!(message.finish_reason, Some(expected));
+        }
+    }
+}
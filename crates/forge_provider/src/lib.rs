@@ -1,12 +1,45 @@
 mod anthropic;
+mod audit;
+mod azure;
+#[cfg(feature = "bedrock")]
+mod bedrock;
+#[cfg(feature = "blocking")]
+mod blocking;
+mod cassette;
+mod circuit_breaker;
 mod client;
+mod cohere;
+mod disk_cache;
 mod error;
 mod forge_provider;
+mod gemini;
+mod load_balancer;
+mod metrics;
+mod middleware;
+#[cfg(test)]
+mod mock_provider;
 #[cfg(test)]
 mod mock_server;
+mod ollama;
+mod rate_limiter;
 mod retry;
+mod router;
+#[cfg(feature = "sse")]
+mod sse;
+mod streaming_timeout;
 
 mod utils;
 
 // Re-export from builder.rs
-pub use client::Client;
+pub use audit::{AuditEntry, AuditSink};
+#[cfg(feature = "blocking")]
+pub use blocking::{BlockingChatIter, BlockingClient};
+pub use cassette::CassetteMode;
+pub use circuit_breaker::CircuitConfig;
+pub use client::{Client, ClientBuilder};
+pub use load_balancer::LoadBalancer;
+pub use middleware::{Middleware, RequestParts, ResponseMeta};
+pub use retry::RetryEvent;
+pub use router::Router;
+#[cfg(feature = "sse")]
+pub use sse::into_sse_stream;
@@ -0,0 +1,179 @@
+use std::time::{Duration, Instant};
+
+use forge_app::domain::{ChatCompletionMessage, Context, Model, ModelId, ResultStream};
+use rand::Rng;
+use tokio::sync::Mutex;
+
+use crate::Client;
+
+/// How long a member stays deweighted after [`LoadBalancer::refresh_health`]
+/// finds it unreachable or unauthenticated, mirroring
+/// `crate::client::DEFAULT_RATE_LIMIT_COOLDOWN`'s role for a rate-limited key.
+const UNHEALTHY_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// One client in a [`LoadBalancer`]: its configured weight, and the instant
+/// (if any) until which it's skipped after `refresh_health` found it
+/// unhealthy.
+struct Member {
+    client: Client,
+    weight: u32,
+    unhealthy_until: Mutex<Option<Instant>>,
+}
+
+/// Spreads chat/model requests across several `Client`s by weighted random
+/// selection, instead of `Client::with_fallbacks`'s strict try-in-order
+/// behavior. Suited to a pool of otherwise-interchangeable providers (e.g.
+/// several API keys or vendors serving the same model) where the goal is to
+/// distribute load roughly according to each one's configured capacity
+/// rather than to fail over from a primary to a backup. A member found
+/// unhealthy by [`LoadBalancer::refresh_health`] is skipped by selection
+/// until its cooldown elapses, falling back to the full set if every member
+/// is currently deweighted so a request is still served.
+pub struct LoadBalancer {
+    members: Vec<Member>,
+}
+
+impl LoadBalancer {
+    /// Builds a `LoadBalancer` over `members`, each paired with its relative
+    /// weight (e.g. `(client_a, 70), (client_b, 30)` sends roughly 70% of
+    /// requests to `client_a`). Weights are relative to each other, not
+    /// percentages - `(1, 1)` and `(70, 70)` behave identically.
+    pub fn new(members: Vec<(Client, u32)>) -> Self {
+        Self {
+            members: members
+                .into_iter()
+                .map(|(client, weight)| Member {
+                    client,
+                    weight,
+                    unhealthy_until: Mutex::new(None),
+                })
+                .collect(),
+        }
+    }
+
+    /// Calls [`Client::health_check`] on every member, deweighting (for
+    /// [`UNHEALTHY_COOLDOWN`]) any that come back unreachable, unauthenticated,
+    /// or erroring outright. A member that's already deweighted and still
+    /// unhealthy just has its cooldown extended from now.
+    pub async fn refresh_health(&self) {
+        for member in &self.members {
+            let healthy = matches!(
+                member.client.health_check().await,
+                Ok(status) if status.reachable && status.authenticated
+            );
+            let mut unhealthy_until = member.unhealthy_until.lock().await;
+            *unhealthy_until =
+                if healthy { None } else { Some(Instant::now() + UNHEALTHY_COOLDOWN) };
+        }
+    }
+
+    /// Weighted-random selection among members not currently deweighted. If
+    /// every member is deweighted (e.g. a full outage), selection falls back
+    /// to the full set rather than refusing to serve the request - the same
+    /// "proceed anyway once nothing looks better" behavior as
+    /// `crate::client::KeyPool::next`.
+    async fn pick(&self) -> &Client {
+        let mut healthy = Vec::with_capacity(self.members.len());
+        for member in &self.members {
+            let until = *member.unhealthy_until.lock().await;
+            if !matches!(until, Some(until) if Instant::now() < until) {
+                healthy.push(member);
+            }
+        }
+        let pool = if healthy.is_empty() { self.members.iter().collect() } else { healthy };
+
+        let total_weight: u32 = pool.iter().map(|member| member.weight).sum();
+        if total_weight == 0 {
+            return &pool[0].client;
+        }
+        let mut roll = rand::thread_rng().gen_range(0..total_weight);
+        for member in &pool {
+            if roll < member.weight {
+                return &member.client;
+            }
+            roll -= member.weight;
+        }
+        &pool[pool.len() - 1].client
+    }
+
+    /// Chats through a single member, picked by weighted random selection
+    /// among those not currently deweighted by [`LoadBalancer::refresh_health`].
+    pub async fn chat(
+        &self,
+        model: &ModelId,
+        context: Context,
+    ) -> ResultStream<ChatCompletionMessage, anyhow::Error> {
+        self.pick().await.chat(model, context).await
+    }
+
+    /// Lists models through a single member, picked the same way as `chat`.
+    pub async fn models(&self) -> anyhow::Result<Vec<Model>> {
+        self.pick().await.models().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use forge_app::domain::{ChatCompletionMessage, Content, RetryConfig};
+
+    use super::*;
+    use crate::mock_provider::MockProvider;
+
+    /// Builds a client that answers `reply` to `calls` successive `chat()`
+    /// calls, since `MockProvider`'s response queue is consumed one entry per
+    /// call.
+    fn mock_client(reply: &str, calls: usize) -> Client {
+        let mut builder = MockProvider::builder();
+        for _ in 0..calls {
+            builder = builder.chat_response(vec![ChatCompletionMessage {
+                content: Some(Content::part(reply)),
+                ..Default::default()
+            }]);
+        }
+        Client::new_mock(builder, Arc::new(RetryConfig::default()))
+    }
+
+    #[tokio::test]
+    async fn test_chat_distributes_roughly_according_to_weights() {
+        let iterations = 1000;
+        let balancer = LoadBalancer::new(vec![
+            (mock_client("a", iterations), 70),
+            (mock_client("b", iterations), 30),
+        ]);
+        let mut tally: HashMap<&str, u32> = HashMap::new();
+
+        for _ in 0..iterations {
+            let mut stream = balancer.chat(&ModelId::new("mock-model"), Context::default()).await.unwrap();
+            let message = futures::StreamExt::next(&mut stream).await.unwrap().unwrap();
+            let reply = message.content.unwrap().as_str().to_string();
+            *tally.entry(if reply == "a" { "a" } else { "b" }).or_default() += 1;
+        }
+
+        let a_share = *tally.get("a").unwrap_or(&0) as f64 / iterations as f64;
+        // 70/30 over 1000 draws should land close to 0.7; ±10 points keeps this
+        // from flaking without being so loose it'd pass a broken weighting too.
+        assert!((0.6..=0.8).contains(&a_share), "a's share was {a_share}, expected ~0.7");
+    }
+
+    #[tokio::test]
+    async fn test_refresh_health_deweights_an_unreachable_member() {
+        let healthy = mock_client("healthy", 20);
+        let unreachable = Client::new_mock(
+            MockProvider::builder().models_not_found(),
+            Arc::new(RetryConfig::default()),
+        );
+        let balancer = LoadBalancer::new(vec![(healthy, 1), (unreachable, 1)]);
+
+        balancer.refresh_health().await;
+
+        for _ in 0..20 {
+            let mut stream =
+                balancer.chat(&ModelId::new("mock-model"), Context::default()).await.unwrap();
+            let message = futures::StreamExt::next(&mut stream).await.unwrap().unwrap();
+            assert_eq!(message.content.unwrap().as_str(), "healthy");
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! Prometheus-compatible counters and histograms recorded through the
+//! `metrics` crate facade, gated behind the `metrics` feature so consumers
+//! who don't want the dependency aren't affected. Wiring up an actual
+//! recorder (e.g. `metrics-exporter-prometheus`) is left to the caller; this
+//! module only records through whatever facade is installed.
+//!
+//! Labels are limited to provider kind and model id to keep cardinality
+//! bounded - neither grows unboundedly with traffic, unlike e.g. a raw error
+//! message or request id would.
+
+#[cfg(feature = "metrics")]
+mod imp {
+    use forge_app::domain::ModelId;
+
+    fn model_label(model: Option<&ModelId>) -> String {
+        model.map(|id| id.to_string()).unwrap_or_default()
+    }
+
+    pub(crate) fn record_request(
+        provider: &'static str,
+        model: Option<&ModelId>,
+        outcome: &'static str,
+    ) {
+        metrics::counter!(
+            "forge_provider_requests_total",
+            "provider" => provider,
+            "model" => model_label(model),
+            "outcome" => outcome,
+        )
+        .increment(1);
+    }
+
+    pub(crate) fn record_duration(provider: &'static str, model: Option<&ModelId>, seconds: f64) {
+        metrics::histogram!(
+            "forge_provider_request_duration_seconds",
+            "provider" => provider,
+            "model" => model_label(model),
+        )
+        .record(seconds);
+    }
+
+    pub(crate) fn record_retry(provider: &'static str) {
+        metrics::counter!("forge_provider_retries_total", "provider" => provider).increment(1);
+    }
+
+    pub(crate) fn record_tokens(provider: &'static str, model: &ModelId, tokens: u64) {
+        metrics::counter!(
+            "forge_provider_tokens_total",
+            "provider" => provider,
+            "model" => model.to_string(),
+        )
+        .increment(tokens);
+    }
+}
+
+#[cfg(not(feature = "metrics"))]
+mod imp {
+    use forge_app::domain::ModelId;
+
+    pub(crate) fn record_request(
+        _provider: &'static str,
+        _model: Option<&ModelId>,
+        _outcome: &'static str,
+    ) {
+    }
+
+    pub(crate) fn record_duration(
+        _provider: &'static str,
+        _model: Option<&ModelId>,
+        _seconds: f64,
+    ) {
+    }
+
+    pub(crate) fn record_retry(_provider: &'static str) {}
+
+    pub(crate) fn record_tokens(_provider: &'static str, _model: &ModelId, _tokens: u64) {}
+}
+
+pub(crate) use imp::{record_duration, record_request, record_retry, record_tokens};
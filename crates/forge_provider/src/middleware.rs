@@ -0,0 +1,33 @@
+use forge_app::domain::ModelId;
+
+/// Describes an outgoing provider call before it's dispatched. Hooks may
+/// mutate `model` to redirect the call to a different model before it
+/// reaches the underlying provider.
+#[derive(Debug, Clone)]
+pub struct RequestParts {
+    pub provider: &'static str,
+    pub operation: &'static str,
+    pub model: Option<ModelId>,
+}
+
+/// Describes the outcome of a provider call once it has been dispatched.
+/// `error` carries the error message when the call failed before a stream
+/// or value could be produced.
+#[derive(Debug, Clone)]
+pub struct ResponseMeta {
+    pub provider: &'static str,
+    pub operation: &'static str,
+    pub model: Option<ModelId>,
+    pub error: Option<String>,
+}
+
+/// Observes or mutates provider calls made through a [`crate::Client`].
+/// Hooks fire for every `chat`, `models`, and `embeddings` call, regardless
+/// of which provider is backing the client, and run in registration order.
+/// Useful for injecting correlation IDs or redacting secrets before
+/// logging, without forking the crate.
+#[async_trait::async_trait]
+pub trait Middleware: Send + Sync {
+    async fn on_request(&self, _req: &mut RequestParts) {}
+    async fn on_response(&self, _resp: &ResponseMeta) {}
+}
@@ -0,0 +1,196 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use forge_app::domain::{BoxStream, ChatCompletionMessage, Context, Model, ModelId};
+use tokio::sync::Mutex;
+
+use crate::error::ProviderError;
+
+/// Canned provider for exercising `Client`'s chat/retry/fallback logic from
+/// this crate's own tests without standing up a mock HTTP server. Scripted
+/// via [`MockProvider::builder`] and wired into a [`crate::Client`] through
+/// `Client::new_mock`, since `Provider` (the public enum `Client::new`
+/// matches on) has no variant for it - a mock provider has no wire format to
+/// speak of.
+#[derive(Clone, Default)]
+pub(crate) struct MockProvider {
+    models: Vec<Model>,
+    chat_responses: Arc<Mutex<VecDeque<Vec<ChatCompletionMessage>>>>,
+    chat_responses_by_model: HashMap<ModelId, Vec<ChatCompletionMessage>>,
+    fail_times: usize,
+    calls: Arc<AtomicUsize>,
+    interrupt_after: Arc<Mutex<Option<usize>>>,
+    models_not_found: bool,
+    delay: Option<Duration>,
+    in_flight: Arc<AtomicUsize>,
+    peak_in_flight: Arc<AtomicUsize>,
+}
+
+impl MockProvider {
+    pub(crate) fn builder() -> Self {
+        Self::default()
+    }
+
+    /// Sets the models `models()` / `refresh_models()` return.
+    pub(crate) fn models(mut self, models: Vec<Model>) -> Self {
+        self.models = models;
+        self
+    }
+
+    /// Queues one scripted stream of messages, returned in FIFO order by
+    /// successive non-failing `chat()` calls. Calling this more than once
+    /// scripts a different response for each successive call.
+    pub(crate) fn chat_response(self, messages: Vec<ChatCompletionMessage>) -> Self {
+        self.chat_responses
+            .try_lock()
+            .expect("builder methods run before the provider is shared")
+            .push_back(messages);
+        self
+    }
+
+    /// Scripts a response returned only for `chat()` calls against `model`,
+    /// independent of and checked before the FIFO queue populated by
+    /// [`MockProvider::chat_response`]. Useful for tests that fan a single
+    /// request out across several models (e.g. `Client::chat_many`) and need
+    /// each one to come back with distinguishable content.
+    pub(crate) fn chat_response_for_model(
+        mut self,
+        model: ModelId,
+        messages: Vec<ChatCompletionMessage>,
+    ) -> Self {
+        self.chat_responses_by_model.insert(model, messages);
+        self
+    }
+
+    /// Makes the first `n` calls to either `chat()` or `models()` fail with a
+    /// [`ProviderError::Upstream`] (status 503) before any scripted response
+    /// is returned, so retry and fallback logic can be exercised
+    /// deterministically against a realistic, classifiable error.
+    pub(crate) fn fail_times(mut self, n: usize) -> Self {
+        self.fail_times = n;
+        self
+    }
+
+    /// Makes the *next* `chat()` call's stream yield only the first `chunks`
+    /// scripted messages before failing mid-stream, simulating a dropped
+    /// connection after some content already arrived. Consumed on first use,
+    /// so a subsequent `chat()` call (e.g. a reconnect) streams its scripted
+    /// response normally.
+    pub(crate) fn interrupt_after(self, chunks: usize) -> Self {
+        self.interrupt_after
+            .try_lock()
+            .expect("builder methods run before the provider is shared")
+            .replace(chunks);
+        self
+    }
+
+    /// Makes every `models()` call fail with a [`ProviderError::Upstream`]
+    /// 404, as if this provider had no `/models` endpoint at all, so
+    /// `Client::with_static_models` fallback logic can be exercised without
+    /// standing up a real HTTP mock. Unlike [`MockProvider::fail_times`],
+    /// this doesn't consume the call counter and never stops failing, since
+    /// a missing endpoint isn't a transient condition.
+    pub(crate) fn models_not_found(mut self) -> Self {
+        self.models_not_found = true;
+        self
+    }
+
+    /// Delays each `chat()` call by `delay` before it resolves, so
+    /// concurrent calls have a window to overlap. `MockProvider` normally
+    /// resolves instantly, which hides everything but the count of
+    /// concurrent chats from a test racing them, such as one exercising
+    /// `Client::with_max_concurrency`.
+    pub(crate) fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Highest number of `chat()` calls this provider has seen in flight at
+    /// once, tracked across the whole `delay` window. Meaningless without
+    /// [`MockProvider::delay`], since without it a call resolves before any
+    /// other call can start.
+    pub(crate) fn peak_in_flight(&self) -> usize {
+        self.peak_in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Total number of `chat()`/`models()` calls this provider has seen,
+    /// including ones that failed via [`MockProvider::fail_times`]. Used to
+    /// assert that a caller-side guard (e.g. a circuit breaker) actually
+    /// fast-failed instead of reaching the provider at all.
+    pub(crate) fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    fn should_fail(&self) -> bool {
+        self.calls.fetch_add(1, Ordering::SeqCst) < self.fail_times
+    }
+
+    fn injected_failure() -> anyhow::Error {
+        ProviderError::Upstream { status: 503, body: "mock provider: injected failure".into() }
+            .into()
+    }
+
+    pub(crate) async fn models_call(&self) -> anyhow::Result<Vec<Model>> {
+        if self.models_not_found {
+            return Err(ProviderError::Upstream {
+                status: 404,
+                body: "mock provider: no /models endpoint".into(),
+            }
+            .into());
+        }
+        if self.should_fail() {
+            return Err(Self::injected_failure());
+        }
+        Ok(self.models.clone())
+    }
+
+    pub(crate) async fn chat_call(
+        &self,
+        model: &ModelId,
+        _context: Context,
+    ) -> anyhow::Result<BoxStream<ChatCompletionMessage, anyhow::Error>> {
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+        if let Some(delay) = self.delay {
+            tokio::time::sleep(delay).await;
+        }
+
+        // A scripted mid-stream interruption takes priority over `fail_times`
+        // for the call it applies to, since `interrupt_after` already scripts
+        // exactly how that call behaves.
+        let interrupt_after = self.interrupt_after.lock().await.take();
+        let result = if interrupt_after.is_none() && self.should_fail() {
+            Err(Self::injected_failure())
+        } else {
+            let messages = match self.chat_responses_by_model.get(model) {
+                Some(messages) => messages.clone(),
+                None => self.chat_responses.lock().await.pop_front().unwrap_or_default(),
+            };
+
+            let items: Vec<anyhow::Result<ChatCompletionMessage>> = match interrupt_after {
+                Some(n) if n < messages.len() => messages
+                    .into_iter()
+                    .take(n)
+                    .map(Ok)
+                    .chain(std::iter::once(Err(Self::injected_failure())))
+                    .collect(),
+                _ => messages.into_iter().map(Ok).collect(),
+            };
+
+            Ok(Box::pin(futures::stream::iter(items)))
+        };
+
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        result
+    }
+
+    pub(crate) async fn embeddings_call(
+        &self,
+        _model: &ModelId,
+        _inputs: Vec<String>,
+    ) -> anyhow::Result<Vec<Vec<f32>>> {
+        anyhow::bail!("MockProvider does not support embeddings")
+    }
+}
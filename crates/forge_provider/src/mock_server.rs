@@ -19,6 +19,146 @@ impl MockServer {
             .await
     }
 
+    /// Like [`MockServer::mock_models`], but serves `gzip_body` (already
+    /// gzip-compressed) with a `Content-Encoding: gzip` header, so a test can
+    /// assert a client with compression enabled transparently decodes it.
+    pub async fn mock_models_gzip(&mut self, gzip_body: Vec<u8>, status: usize) -> Mock {
+        self.server
+            .mock("GET", "/models")
+            .with_status(status)
+            .with_header("content-type", "application/json")
+            .with_header("content-encoding", "gzip")
+            .with_body(gzip_body)
+            .create_async()
+            .await
+    }
+
+    /// Like [`MockServer::mock_models`], but only matches a request with no
+    /// `after` cursor at all - the first page of a paginated listing.
+    pub async fn mock_models_first_page(&mut self, body: serde_json::Value, status: usize) -> Mock {
+        self.server
+            .mock("GET", "/models")
+            .match_query(mockito::Matcher::Missing)
+            .with_status(status)
+            .with_header("content-type", "application/json")
+            .with_body(body.to_string())
+            .create_async()
+            .await
+    }
+
+    /// Like [`MockServer::mock_models`], but only matches a request whose
+    /// `after` cursor is exactly `after`, so a test can serve a different
+    /// page per cursor value.
+    pub async fn mock_models_page(
+        &mut self,
+        after: &str,
+        body: serde_json::Value,
+        status: usize,
+    ) -> Mock {
+        self.server
+            .mock("GET", "/models")
+            .match_query(mockito::Matcher::UrlEncoded("after".into(), after.into()))
+            .with_status(status)
+            .with_header("content-type", "application/json")
+            .with_body(body.to_string())
+            .create_async()
+            .await
+    }
+
+    /// Like [`MockServer::mock_models`], but only matches requests carrying
+    /// `Authorization: Bearer <key>`, so a test can tell which of several
+    /// rotating keys a given request went out under.
+    pub async fn mock_models_for_key(
+        &mut self,
+        key: &str,
+        body: serde_json::Value,
+        status: usize,
+    ) -> Mock {
+        self.server
+            .mock("GET", "/models")
+            .match_header("authorization", format!("Bearer {key}").as_str())
+            .with_status(status)
+            .with_header("content-type", "application/json")
+            .with_body(body.to_string())
+            .create_async()
+            .await
+    }
+
+    pub async fn mock_chat_completions_stream(&mut self, events: &[serde_json::Value]) -> Mock {
+        let mut body = String::new();
+        for event in events {
+            body.push_str(&format!("data: {event}\n\n"));
+        }
+        body.push_str("data: [DONE]\n\n");
+
+        self.server
+            .mock("POST", "/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "text/event-stream")
+            .with_body(body)
+            .create_async()
+            .await
+    }
+
+    /// Like [`MockServer::mock_chat_completions_stream`], but serves `body`
+    /// verbatim instead of building it from a list of JSON events, so a test
+    /// can exercise raw SSE framing - comment/heartbeat lines, blank lines,
+    /// trailing whitespace - that the JSON-event helper can't express.
+    pub async fn mock_chat_completions_raw_stream(&mut self, body: &str) -> Mock {
+        self.server
+            .mock("POST", "/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "text/event-stream")
+            .with_body(body)
+            .create_async()
+            .await
+    }
+
+    /// Mocks a non-streaming chat completion response, i.e. a plain JSON
+    /// body rather than the `data: ...` SSE framing `mock_chat_completions_stream`
+    /// produces.
+    pub async fn mock_chat_completions_json(&mut self, body: serde_json::Value) -> Mock {
+        self.server
+            .mock("POST", "/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body.to_string())
+            .create_async()
+            .await
+    }
+
+    /// Like [`MockServer::mock_chat_completions_json`], but also sets
+    /// `header_name: header_value` on the response, so a test can assert a
+    /// provider picks up a response header (e.g. a `request-id`).
+    pub async fn mock_chat_completions_json_with_header(
+        &mut self,
+        body: serde_json::Value,
+        header_name: &str,
+        header_value: &str,
+    ) -> Mock {
+        self.server
+            .mock("POST", "/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_header(header_name, header_value)
+            .with_body(body.to_string())
+            .create_async()
+            .await
+    }
+
+    /// Mocks a Mistral Codestral FIM completion response at
+    /// `/fim/completions`, distinct from `/chat/completions` so a test can
+    /// assert the FIM endpoint (not the chat one) was hit.
+    pub async fn mock_fim_completions_json(&mut self, body: serde_json::Value) -> Mock {
+        self.server
+            .mock("POST", "/fim/completions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body.to_string())
+            .create_async()
+            .await
+    }
+
     pub fn url(&self) -> String {
         self.server.url()
     }
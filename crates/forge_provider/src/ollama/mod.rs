@@ -0,0 +1,5 @@
+mod provider;
+mod request;
+mod response;
+
+pub use provider::Ollama;
@@ -0,0 +1,181 @@
+use anyhow::Context as _;
+use derive_builder::Builder;
+use forge_app::domain::{
+    ChatCompletionMessage, Context as ChatContext, Model, ModelId, ResultStream,
+};
+use reqwest::{Client, Url};
+use tokio_stream::StreamExt;
+use tracing::debug;
+
+use super::request::Request;
+use super::response::{Response, TagsResponse};
+use crate::error::Error;
+use crate::utils::{format_http_context, ndjson_lines};
+
+#[derive(Clone, Builder)]
+pub struct Ollama {
+    client: Client,
+    base_url: Url,
+}
+
+impl Ollama {
+    pub fn builder() -> OllamaBuilder {
+        OllamaBuilder::default()
+    }
+
+    fn url(&self, path: &str) -> anyhow::Result<Url> {
+        self.base_url
+            .join(path)
+            .with_context(|| format!("Failed to append {} to base URL: {}", path, self.base_url))
+    }
+
+    /// Builds the exact JSON body `chat()` would send for `model`/`context`,
+    /// without performing any I/O. Useful for diagnosing why a provider
+    /// rejects a payload, since it reflects the same serialization `chat()`
+    /// uses.
+    pub fn build_chat_request(
+        &self,
+        model: &ModelId,
+        context: ChatContext,
+    ) -> anyhow::Result<serde_json::Value> {
+        let request = Request::new(model.clone(), context);
+        let url = self.url("api/chat")?;
+
+        Ok(serde_json::json!({
+            "url": url.to_string(),
+            "headers": {},
+            "body": request,
+        }))
+    }
+
+    pub async fn chat(
+        &self,
+        model: &ModelId,
+        context: ChatContext,
+    ) -> ResultStream<ChatCompletionMessage, anyhow::Error> {
+        let request = Request::new(model.clone(), context);
+        let url = self.url("api/chat")?;
+
+        debug!(url = %url, model = %model, "Connecting Upstream");
+
+        let response = self
+            .client
+            .post(url.clone())
+            .json(&request)
+            .send()
+            .await
+            .with_context(|| format_http_context(None, "POST", &url))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.ok();
+            return Err(Error::InvalidStatusCode(status.as_u16()))
+                .with_context(|| match body {
+                    Some(body) => format!("{status} Reason: {body}"),
+                    None => format!("{status} Reason: [Unknown]"),
+                })
+                .with_context(|| format_http_context(Some(status), "POST", &url));
+        }
+
+        let stream = ndjson_lines(response.bytes_stream()).map(move |line| {
+            line.with_context(|| format_http_context(None, "POST", &url))
+                .and_then(|line| {
+                    serde_json::from_str::<Response>(&line)
+                        .with_context(|| format!("Failed to parse Ollama event: {line}"))
+                })
+                .and_then(ChatCompletionMessage::try_from)
+        });
+
+        Ok(Box::pin(stream))
+    }
+
+    /// Ollama's `/api/tags` lists the models pulled onto the local server;
+    /// there's no separate "available models" catalog to fetch.
+    pub async fn models(&self) -> anyhow::Result<Vec<Model>> {
+        let url = self.url("api/tags")?;
+        debug!(url = %url, "Fetching models");
+
+        let response = self
+            .client
+            .get(url.clone())
+            .send()
+            .await
+            .with_context(|| format_http_context(None, "GET", &url))
+            .with_context(|| "Failed to fetch models")?;
+
+        let status = response.status();
+        let ctx_msg = format_http_context(Some(status), "GET", &url);
+        let text = response
+            .text()
+            .await
+            .with_context(|| ctx_msg.clone())
+            .with_context(|| "Failed to decode response into text")?;
+
+        if !status.is_success() {
+            return Err(anyhow::anyhow!(text))
+                .with_context(|| ctx_msg)
+                .with_context(|| "Failed to fetch the models");
+        }
+
+        let response: TagsResponse = serde_json::from_str(&text)
+            .with_context(|| ctx_msg)
+            .with_context(|| "Failed to deserialize models response")?;
+
+        Ok(response
+            .models
+            .into_iter()
+            .map(|tag| Model {
+                id: ModelId::new(tag.name),
+                name: None,
+                description: None,
+                context_length: None,
+                tools_supported: None,
+                supports_parallel_tool_calls: None,
+                supports_reasoning: None,
+                supports_vision: None,
+                deprecated: None,
+            })
+            .collect())
+    }
+
+    pub async fn embeddings(
+        &self,
+        _model: &ModelId,
+        _inputs: Vec<String>,
+    ) -> anyhow::Result<Vec<Vec<f32>>> {
+        anyhow::bail!("Ollama embeddings are not yet supported by this client")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockito::Server;
+    use pretty_assertions::assert_eq;
+    use reqwest::Client;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_models_targets_api_tags() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/tags")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"models":[{"name":"llama3"}]}"#)
+            .create_async()
+            .await;
+
+        let ollama = Ollama::builder()
+            .client(Client::new())
+            .base_url(Url::parse(&format!("{}/", server.url())).unwrap())
+            .build()
+            .unwrap();
+
+        let models = ollama.models().await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].id, ModelId::new("llama3"));
+    }
+}
@@ -0,0 +1,124 @@
+use forge_app::domain::{
+    Context, ContextMessage, ModelId, Role, ToolCallFull, ToolDefinition, ToolName, ToolOutput,
+    ToolValue,
+};
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct Request {
+    pub model: ModelId,
+    pub messages: Vec<Message>,
+    pub stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<Tool>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Message {
+    pub role: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCall>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ToolCall {
+    pub function: FunctionCall,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FunctionCall {
+    pub name: ToolName,
+    pub arguments: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Tool {
+    pub r#type: &'static str,
+    pub function: FunctionDescription,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FunctionDescription {
+    pub name: String,
+    pub description: String,
+    pub parameters: serde_json::Value,
+}
+
+impl From<ToolDefinition> for Tool {
+    fn from(value: ToolDefinition) -> Self {
+        Tool {
+            r#type: "function",
+            function: FunctionDescription {
+                name: value.name.to_string(),
+                description: value.description,
+                parameters: serde_json::to_value(value.input_schema).unwrap(),
+            },
+        }
+    }
+}
+
+impl From<ToolCallFull> for ToolCall {
+    fn from(value: ToolCallFull) -> Self {
+        ToolCall { function: FunctionCall { name: value.name, arguments: value.arguments } }
+    }
+}
+
+impl Request {
+    /// Ollama's `/api/chat` takes the model name inside the request body
+    /// rather than in the URL, so it's built from both the context and the
+    /// target model up front.
+    pub fn new(model: ModelId, context: Context) -> Self {
+        let messages = context.messages.into_iter().map(Message::from).collect();
+        let tools = context
+            .tools
+            .into_iter()
+            .map(Tool::from)
+            .collect::<Vec<_>>();
+
+        Request { model, messages, stream: true, tools: (!tools.is_empty()).then_some(tools) }
+    }
+}
+
+impl From<ContextMessage> for Message {
+    fn from(message: ContextMessage) -> Self {
+        match message {
+            ContextMessage::Text(text) => {
+                let role = match text.role {
+                    Role::System => "system",
+                    Role::User => "user",
+                    Role::Assistant => "assistant",
+                };
+
+                Message {
+                    role: role.to_string(),
+                    content: Some(text.content),
+                    tool_calls: text
+                        .tool_calls
+                        .map(|calls| calls.into_iter().map(ToolCall::from).collect()),
+                }
+            }
+            ContextMessage::Tool(tool_result) => Message {
+                role: "tool".to_string(),
+                content: Some(tool_output_to_text(&tool_result.output)),
+                tool_calls: None,
+            },
+            ContextMessage::Image(_) => {
+                Message { role: "user".to_string(), content: Some(String::new()), tool_calls: None }
+            }
+        }
+    }
+}
+
+fn tool_output_to_text(output: &ToolOutput) -> String {
+    output
+        .values
+        .iter()
+        .filter_map(|value| match value {
+            ToolValue::Text(text) => Some(text.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
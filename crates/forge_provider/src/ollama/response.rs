@@ -0,0 +1,80 @@
+use forge_app::domain::{
+    ChatCompletionMessage, Content, FinishReason, ToolCallFull, ToolName, Usage,
+};
+use serde::{Deserialize, Serialize};
+
+/// One NDJSON line streamed back from `/api/chat`. Ollama sends a `message`
+/// delta on every line and sets `done` on the final one, which also carries
+/// the token counts for the whole exchange.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct Response {
+    #[serde(default)]
+    pub message: ResponseMessage,
+    #[serde(default)]
+    pub done: bool,
+    pub prompt_eval_count: Option<usize>,
+    pub eval_count: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ResponseMessage {
+    pub content: Option<String>,
+    pub tool_calls: Option<Vec<ToolCall>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ToolCall {
+    pub function: FunctionCall,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct FunctionCall {
+    pub name: ToolName,
+    pub arguments: serde_json::Value,
+}
+
+/// A single entry from `/api/tags`.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TagsResponse {
+    pub models: Vec<TagModel>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TagModel {
+    pub name: String,
+}
+
+impl TryFrom<Response> for ChatCompletionMessage {
+    type Error = anyhow::Error;
+
+    fn try_from(response: Response) -> Result<Self, Self::Error> {
+        let mut message = ChatCompletionMessage::assistant(Content::full(
+            response.message.content.unwrap_or_default(),
+        ));
+
+        for tool_call in response.message.tool_calls.into_iter().flatten() {
+            message = message.add_tool_call(ToolCallFull {
+                call_id: None,
+                name: tool_call.function.name,
+                arguments: tool_call.function.arguments,
+            });
+        }
+
+        if response.done {
+            message = message.finish_reason_opt(Some(FinishReason::Stop));
+        }
+
+        if let (Some(prompt_tokens), Some(completion_tokens)) =
+            (response.prompt_eval_count, response.eval_count)
+        {
+            message.usage = Some(Usage {
+                prompt_tokens,
+                completion_tokens,
+                total_tokens: prompt_tokens + completion_tokens,
+                ..Default::default()
+            });
+        }
+
+        Ok(message)
+    }
+}
@@ -0,0 +1,132 @@
+use std::time::{Duration, Instant};
+
+use tokio::sync::Mutex;
+use tokio::time::sleep;
+
+/// A single token bucket: `capacity` tokens refilled continuously at
+/// `refill_per_sec`, never exceeding `capacity`. `acquire` blocks until
+/// enough tokens have accumulated rather than rejecting the caller, since
+/// [`RateLimiter`] is meant to pace requests, not drop them.
+struct Bucket {
+    capacity: f64,
+    refill_per_sec: f64,
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl Bucket {
+    fn new(capacity: f64, refill_per_sec: f64) -> Self {
+        Self { capacity, refill_per_sec, tokens: capacity, updated_at: Instant::now() }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.updated_at = now;
+    }
+
+    /// Returns how long the caller must wait for `cost` tokens to become
+    /// available, deducting them immediately if they already are.
+    fn wait_for(&mut self, cost: f64) -> Duration {
+        self.refill();
+        if self.tokens >= cost {
+            self.tokens -= cost;
+            return Duration::ZERO;
+        }
+        let missing = cost - self.tokens;
+        self.tokens = 0.0;
+        Duration::from_secs_f64(missing / self.refill_per_sec)
+    }
+}
+
+/// Opt-in client-side pacing for outbound provider calls, installed via
+/// [`crate::Client::with_rate_limit`]. Paces on a request-count bucket and,
+/// optionally, a separate tokens-per-minute bucket sized off prompt length,
+/// so a handful of huge prompts can't blow through a budget sized for small
+/// ones. Cloning the owning `Client` shares the same `RateLimiter` (it's
+/// stored behind an `Arc`), so the budget holds across every clone rather
+/// than resetting per instance.
+pub struct RateLimiter {
+    requests: Mutex<Bucket>,
+    tokens: Option<Mutex<Bucket>>,
+}
+
+impl RateLimiter {
+    /// `requests_per_minute` and `burst` configure the request-count bucket:
+    /// `burst` is its capacity and `requests_per_minute / 60` its refill
+    /// rate. `tokens_per_minute`, if set, adds a second bucket whose capacity
+    /// is one minute's worth of tokens, drawn down by `acquire`'s
+    /// caller-supplied token cost.
+    pub fn new(requests_per_minute: u32, burst: u32, tokens_per_minute: Option<u32>) -> Self {
+        Self {
+            requests: Mutex::new(Bucket::new(burst as f64, requests_per_minute as f64 / 60.0)),
+            tokens: tokens_per_minute
+                .map(|tpm| Mutex::new(Bucket::new(tpm as f64, tpm as f64 / 60.0))),
+        }
+    }
+
+    /// Blocks until both the request-count bucket has a permit and, if
+    /// configured, the tokens-per-minute bucket has `token_cost` tokens
+    /// available.
+    pub async fn acquire(&self, token_cost: u64) {
+        let wait = self.requests.lock().await.wait_for(1.0);
+        if !wait.is_zero() {
+            sleep(wait).await;
+        }
+
+        if let Some(tokens) = &self.tokens {
+            let wait = tokens.lock().await.wait_for(token_cost as f64);
+            if !wait.is_zero() {
+                sleep(wait).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_acquire_paces_requests_past_the_initial_burst() {
+        let limiter = Arc::new(RateLimiter::new(60, 1, None));
+        let started = Instant::now();
+
+        // burst=1 covers the first call for free; the second has to wait for
+        // the bucket to refill at 60/min, i.e. roughly one second.
+        limiter.acquire(0).await;
+        limiter.acquire(0).await;
+
+        assert!(started.elapsed() >= Duration::from_millis(900));
+    }
+
+    #[tokio::test]
+    async fn test_acquire_does_not_wait_within_the_burst() {
+        let limiter = RateLimiter::new(60, 5, None);
+        let started = Instant::now();
+
+        for _ in 0..5 {
+            limiter.acquire(0).await;
+        }
+
+        assert!(started.elapsed() < Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_acquire_paces_on_the_tokens_per_minute_budget() {
+        // High requests_per_minute/burst keeps the request bucket out of the
+        // way; a 120-tokens-per-minute budget refills at 2/sec, so draining
+        // it completely and then asking for one more token forces a ~0.5s
+        // wait.
+        let limiter = RateLimiter::new(6000, 6000, Some(120));
+        let started = Instant::now();
+
+        limiter.acquire(120).await;
+        limiter.acquire(1).await;
+
+        assert!(started.elapsed() >= Duration::from_millis(400));
+    }
+}
@@ -1,30 +1,230 @@
-use forge_app::domain::{Error as DomainError, RetryConfig};
-
-use crate::error::{Error, ErrorResponse};
+use std::time::Duration;
+
+use forge_app::domain::{Error as DomainError, ModelId, RetryConfig};
+
+use crate::error::{Error, ErrorResponse, ProviderError, TimeoutPhase};
+
+/// Snapshot of a single retry decision, passed to callbacks registered via
+/// [`crate::Client::on_retry`]. Built from the same classification
+/// `mark_retryable` already logs a `tracing::warn!` for, so a caller that
+/// wants retries in a dashboard doesn't have to scrape log output.
+#[derive(Debug, Clone)]
+pub struct RetryEvent {
+    /// The ordinal `Client` assigned the call that failed - a
+    /// monotonically increasing counter shared with the `attempt` field on
+    /// every `chat`/`models`/`embeddings` tracing span, so it can be
+    /// correlated with logs from the same call.
+    pub attempt: u64,
+    /// The configured floor delay for this error's status/class, not the
+    /// jittered/exponentially-scaled delay actually slept - that's computed
+    /// one layer up, in `forge_app::retry::retry_with_config`, which this
+    /// crate has no visibility into.
+    pub delay: Duration,
+    /// The error that triggered this retry, rendered with [`ToString`] since
+    /// `anyhow::Error` isn't `Clone`.
+    pub error: String,
+    /// The model the failed call was made against, if the caller supplied
+    /// one (e.g. absent for `refresh_models`).
+    pub model: Option<ModelId>,
+}
 
 const TRANSPORT_ERROR_CODES: [&str; 3] = ["ERR_STREAM_PREMATURE_CLOSE", "ECONNRESET", "ETIMEDOUT"];
 
 pub fn into_retry(error: anyhow::Error, retry_config: &RetryConfig) -> anyhow::Error {
+    if let Some(is_retryable) = classify_provider_error(&error, retry_config) {
+        if is_retryable {
+            return mark_retryable(error, retry_config);
+        }
+        return error;
+    }
+
     if let Some(code) = get_req_status_code(&error)
         .or(get_event_req_status_code(&error))
         .or(get_api_status_code(&error))
     {
         if retry_config.retry_status_codes.contains(&code) {
-            return DomainError::Retryable(error).into();
+            return mark_retryable(error, retry_config);
         }
     }
 
-    if is_api_transport_error(&error)
-        || is_req_transport_error(&error)
-        || is_event_transport_error(&error)
-        || is_empty_error(&error)
+    if let Some(phase) = timeout_phase(&error) {
+        return mark_retryable(ProviderError::Timeout { phase }.into(), retry_config);
+    }
+
+    if is_api_transport_error(&error) || is_event_transport_error(&error) || is_empty_error(&error)
     {
-        return DomainError::Retryable(error).into();
+        return mark_retryable(error, retry_config);
     }
 
     error
 }
 
+/// Classifies the connect-vs-read phase of a timeout or connection failure
+/// buried in `error`'s chain, checking both a raw [`reqwest::Error`] (the
+/// non-streaming path) and a [`reqwest_eventsource::Error::Transport`]'s
+/// inner [`reqwest::Error`] (the streaming path). Returns `None` for
+/// anything that isn't a connect failure or timeout, so callers can fall
+/// back to the generic transport-error classification.
+fn timeout_phase(error: &anyhow::Error) -> Option<TimeoutPhase> {
+    if let Some(reqwest_error) = error.downcast_ref::<reqwest::Error>() {
+        return classify_reqwest_timeout(reqwest_error);
+    }
+    if let Some(reqwest_eventsource::Error::Transport(inner)) =
+        error.downcast_ref::<reqwest_eventsource::Error>()
+    {
+        return classify_reqwest_timeout(inner);
+    }
+    None
+}
+
+fn classify_reqwest_timeout(error: &reqwest::Error) -> Option<TimeoutPhase> {
+    if error.is_connect() {
+        Some(TimeoutPhase::Connect)
+    } else if error.is_timeout() {
+        Some(TimeoutPhase::Read)
+    } else {
+        None
+    }
+}
+
+/// Wraps `error` as a [`DomainError::Retryable`] and emits a `tracing` event
+/// carrying the reason and the backoff delay picked for it. This is the only
+/// place in `forge_provider` that knows a call is about to be retried, since
+/// the loop that actually re-issues the call lives one layer up, in
+/// `forge_app::retry`.
+fn mark_retryable(error: anyhow::Error, retry_config: &RetryConfig) -> anyhow::Error {
+    let (min_delay_ms, max_delay) = backoff_for(&error, retry_config);
+    tracing::warn!(
+        error = %error,
+        min_delay_ms,
+        max_delay,
+        "provider call failed, scheduling retry"
+    );
+    DomainError::Retryable(error).into()
+}
+
+/// The base/max backoff delay to use for retrying `error`: `retry_config`'s
+/// [`RetryConfig::per_status_backoff`] entry for `error`'s status, if any,
+/// otherwise the global `min_delay_ms`/`max_delay`. Checks the same status
+/// sources as `into_retry`'s classification step, plus a typed
+/// `ProviderError::Upstream`.
+pub(crate) fn backoff_for(error: &anyhow::Error, retry_config: &RetryConfig) -> (u64, Option<u64>) {
+    let status = match error.downcast_ref::<ProviderError>() {
+        Some(ProviderError::Upstream { status, .. }) => Some(*status),
+        _ => get_req_status_code(error)
+            .or_else(|| get_event_req_status_code(error))
+            .or_else(|| get_api_status_code(error)),
+    };
+
+    match status {
+        Some(status) => retry_config.backoff_for_status(status),
+        None => (retry_config.min_delay_ms, retry_config.max_delay),
+    }
+}
+
+/// Classifies a `ProviderError`, if `error` downcasts to one, into whether it
+/// should be retried. Returns `None` when `error` isn't a `ProviderError`, so
+/// callers can fall back to the legacy string/code-sniffing classification.
+/// Consults `retry_config`'s override lists before falling back to
+/// [`is_retryable`]'s built-in classification.
+fn classify_provider_error(error: &anyhow::Error, retry_config: &RetryConfig) -> Option<bool> {
+    error.downcast_ref::<ProviderError>().map(|error| {
+        if let ProviderError::Upstream { status, .. } = error {
+            if retry_config.additional_non_retryable_status_codes.contains(status) {
+                return false;
+            }
+            if retry_config.additional_retryable_status_codes.contains(status) {
+                return true;
+            }
+        }
+        is_retryable(error)
+    })
+}
+
+/// Explicitly classifies a `ProviderError` as retryable or terminal,
+/// independent of any `RetryConfig` overrides (see `classify_provider_error`,
+/// which consults those first). 408/429/500/502/503/504 and
+/// connection/timeout errors are retryable; 400/401/403/404/422 and anything
+/// else unrecognized are terminal.
+pub fn is_retryable(error: &ProviderError) -> bool {
+    match error {
+        ProviderError::RateLimited { .. }
+        | ProviderError::Timeout { .. }
+        | ProviderError::FirstTokenTimeout
+        | ProviderError::InterTokenTimeout
+        | ProviderError::InBandError { .. } => true,
+        ProviderError::Unauthorized
+        | ProviderError::ModelNotFound(_)
+        | ProviderError::ContextLengthExceeded { .. }
+        | ProviderError::RequestTooLarge { .. }
+        | ProviderError::MalformedJsonResponse { .. }
+        | ProviderError::NoDefaultModel
+        | ProviderError::VisionNotSupported { .. }
+        | ProviderError::RawEventsUnsupported
+        | ProviderError::KeepAliveEventsUnsupported
+        | ProviderError::ShuttingDown
+        | ProviderError::CassetteMiss { .. }
+        | ProviderError::CircuitOpen => false,
+        ProviderError::Upstream { status, .. } => {
+            matches!(status, 408 | 429 | 500 | 502 | 503 | 504)
+        }
+        ProviderError::Network(error) => error.is_timeout() || error.is_connect(),
+    }
+}
+
+/// Extracts the suggested retry delay carried on a `ProviderError`, if any.
+/// Callers that compute their own backoff (e.g. `forge_app::retry`) should
+/// prefer this over a computed delay from `RetryConfig` when present, since
+/// it reflects the upstream's actual `Retry-After` header.
+pub fn retry_after(error: &anyhow::Error) -> Option<Duration> {
+    match error.downcast_ref::<ProviderError>() {
+        Some(ProviderError::RateLimited { retry_after }) => *retry_after,
+        _ => None,
+    }
+}
+
+/// True if `error` indicates the caller is being rate-limited - either a
+/// typed [`ProviderError::RateLimited`] or a plain HTTP 429 surfaced through
+/// any of the status-code representations below. Checked against the raw,
+/// pre-[`into_retry`] dispatch error, since 429 is retryable and would
+/// otherwise already be wrapped in a [`DomainError::Retryable`] by the time
+/// a caller gets to inspect it. Used by `Client`'s rotating-key pool to park
+/// an exhausted key instead of handing it the next request.
+pub(crate) fn is_rate_limited(error: &anyhow::Error) -> bool {
+    if matches!(error.downcast_ref::<ProviderError>(), Some(ProviderError::RateLimited { .. })) {
+        return true;
+    }
+    if matches!(
+        get_req_status_code(error)
+            .or_else(|| get_event_req_status_code(error))
+            .or_else(|| get_api_status_code(error)),
+        Some(429)
+    ) {
+        return true;
+    }
+    // Most `models()`/`chat()` error paths don't carry a typed status at
+    // all (see `classify_provider_error`'s `None` case above), only the
+    // `"429 ..."` prefix `format_http_context` attaches to the context
+    // chain - the same situation `Client::health_check`'s
+    // `looks_like_unauthorized` works around for 401/403.
+    error.chain().any(|cause| cause.to_string().starts_with("429 "))
+}
+
+/// Parses the value of an HTTP `Retry-After` header, which is either a
+/// number of seconds (e.g. `"120"`) or an HTTP-date (e.g.
+/// `"Wed, 21 Oct 2015 07:28:00 GMT"`). Returns `None` if the value is
+/// malformed or the HTTP-date is already in the past.
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let target = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let now = chrono::Utc::now();
+    (target.to_utc() - now).to_std().ok()
+}
+
 fn get_api_status_code(error: &anyhow::Error) -> Option<u16> {
     error.downcast_ref::<Error>().and_then(|error| match error {
         Error::Response(error) => error
@@ -95,12 +295,6 @@ fn is_empty_error(error: &anyhow::Error) -> bool {
     })
 }
 
-fn is_req_transport_error(error: &anyhow::Error) -> bool {
-    error
-        .downcast_ref::<reqwest::Error>()
-        .is_some_and(|e| e.is_timeout() || e.is_connect())
-}
-
 fn is_event_transport_error(error: &anyhow::Error) -> bool {
     error
         .downcast_ref::<reqwest_eventsource::Error>()
@@ -109,10 +303,13 @@ fn is_event_transport_error(error: &anyhow::Error) -> bool {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use anyhow::anyhow;
+    use forge_app::domain::BackoffOverride;
 
     use super::*;
-    use crate::error::{Error, ErrorCode, ErrorResponse};
+    use crate::error::{Error, ErrorCode, ErrorResponse, ProviderError, TimeoutPhase};
 
     // Helper function to check if an error is retryable
     fn is_retryable(error: anyhow::Error) -> bool {
@@ -123,6 +320,17 @@ mod tests {
         }
     }
 
+    /// Unwraps the `ProviderError` carried inside a `DomainError::Retryable`,
+    /// i.e. the error shape `mark_retryable` produces, so tests can assert on
+    /// the typed error `into_retry` classified rather than just whether it
+    /// was marked retryable.
+    fn retryable_provider_error(error: &anyhow::Error) -> Option<&ProviderError> {
+        match error.downcast_ref::<DomainError>() {
+            Some(DomainError::Retryable(inner)) => inner.downcast_ref::<ProviderError>(),
+            _ => None,
+        }
+    }
+
     #[test]
     fn test_into_retry_with_matching_api_status_code() {
         // Setup
@@ -392,6 +600,168 @@ mod tests {
         assert!(!actual);
     }
 
+    #[test]
+    fn test_into_retry_with_provider_error_rate_limited() {
+        let retry_config = RetryConfig::default();
+        let error = anyhow::Error::from(ProviderError::RateLimited { retry_after: None });
+
+        let actual = into_retry(error, &retry_config);
+
+        assert!(is_retryable(actual));
+    }
+
+    #[test]
+    fn test_into_retry_with_provider_error_unauthorized() {
+        let retry_config = RetryConfig::default();
+        let error = anyhow::Error::from(ProviderError::Unauthorized);
+
+        let actual = into_retry(error, &retry_config);
+
+        assert!(!is_retryable(actual));
+    }
+
+    #[test]
+    fn test_into_retry_with_provider_error_upstream_matching_status() {
+        let retry_config = RetryConfig::default().retry_status_codes(vec![503]);
+        let error = anyhow::Error::from(ProviderError::Upstream {
+            status: 503,
+            body: "unavailable".to_string(),
+        });
+
+        let actual = into_retry(error, &retry_config);
+
+        assert!(is_retryable(actual));
+    }
+
+    #[test]
+    fn test_into_retry_with_provider_error_upstream_non_matching_status() {
+        let retry_config = RetryConfig::default().retry_status_codes(vec![503]);
+        let error = anyhow::Error::from(ProviderError::Upstream {
+            status: 400,
+            body: "bad request".to_string(),
+        });
+
+        let actual = into_retry(error, &retry_config);
+
+        assert!(!is_retryable(actual));
+    }
+
+    #[test]
+    fn test_is_retryable_status_codes() {
+        let retryable = [408, 429, 500, 502, 503, 504];
+        let terminal = [400, 401, 403, 404, 422];
+
+        for status in retryable {
+            let error = ProviderError::Upstream { status, body: String::new() };
+            assert!(super::is_retryable(&error), "{status} should be retryable");
+        }
+
+        for status in terminal {
+            let error = ProviderError::Upstream { status, body: String::new() };
+            assert!(!super::is_retryable(&error), "{status} should be terminal");
+        }
+    }
+
+    #[test]
+    fn test_is_retryable_unrecognized_status_code_defaults_to_terminal() {
+        let error = ProviderError::Upstream { status: 418, body: String::new() };
+        assert!(!super::is_retryable(&error));
+    }
+
+    #[test]
+    fn test_is_retryable_connection_and_timeout_errors() {
+        assert!(super::is_retryable(&ProviderError::Timeout { phase: TimeoutPhase::Read }));
+        assert!(super::is_retryable(&ProviderError::FirstTokenTimeout));
+        assert!(super::is_retryable(&ProviderError::InterTokenTimeout));
+        assert!(super::is_retryable(&ProviderError::RateLimited { retry_after: None }));
+    }
+
+    #[test]
+    fn test_into_retry_with_additional_retryable_status_code_override() {
+        // 418 is terminal by default; the override marks it retryable.
+        let retry_config = RetryConfig::default().additional_retryable_status_codes(vec![418]);
+        let error = anyhow::Error::from(ProviderError::Upstream {
+            status: 418,
+            body: "I'm a teapot".to_string(),
+        });
+
+        let actual = into_retry(error, &retry_config);
+
+        assert!(is_retryable(actual));
+    }
+
+    #[test]
+    fn test_into_retry_with_additional_non_retryable_status_code_override() {
+        // 503 is retryable by default; the override marks it terminal.
+        let retry_config =
+            RetryConfig::default().additional_non_retryable_status_codes(vec![503]);
+        let error = anyhow::Error::from(ProviderError::Upstream {
+            status: 503,
+            body: "unavailable".to_string(),
+        });
+
+        let actual = into_retry(error, &retry_config);
+
+        assert!(!is_retryable(actual));
+    }
+
+    #[test]
+    fn test_into_retry_non_retryable_override_takes_precedence_over_retryable_override() {
+        // When a status appears in both override lists, non-retryable wins.
+        let retry_config = RetryConfig::default()
+            .additional_retryable_status_codes(vec![503])
+            .additional_non_retryable_status_codes(vec![503]);
+        let error = anyhow::Error::from(ProviderError::Upstream {
+            status: 503,
+            body: "unavailable".to_string(),
+        });
+
+        let actual = into_retry(error, &retry_config);
+
+        assert!(!is_retryable(actual));
+    }
+
+    #[test]
+    fn test_parse_retry_after_seconds_form() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn test_parse_retry_after_seconds_form_with_whitespace() {
+        assert_eq!(parse_retry_after(" 5 "), Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_parse_retry_after_http_date_form() {
+        let future = chrono::Utc::now() + chrono::Duration::seconds(60);
+        let header = future.format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+
+        let actual = parse_retry_after(&header).expect("should parse HTTP-date");
+
+        // Allow a little slack for the time elapsed while formatting/parsing.
+        assert!(actual.as_secs() <= 60 && actual.as_secs() >= 58);
+    }
+
+    #[test]
+    fn test_parse_retry_after_invalid_value() {
+        assert_eq!(parse_retry_after("not-a-valid-value"), None);
+    }
+
+    #[test]
+    fn test_retry_after_extracts_duration_from_provider_error() {
+        let error = anyhow::Error::from(ProviderError::RateLimited {
+            retry_after: Some(Duration::from_secs(30)),
+        });
+
+        assert_eq!(retry_after(&error), Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_retry_after_none_for_other_errors() {
+        let error = anyhow::anyhow!("some other error");
+        assert_eq!(retry_after(&error), None);
+    }
+
     #[test]
     fn test_is_empty_error_with_non_response_error() {
         // Setup
@@ -403,4 +773,133 @@ mod tests {
         // Verify
         assert!(!actual);
     }
+
+    #[tracing_test::traced_test]
+    #[test]
+    fn test_into_retry_logs_event_with_error_reason() {
+        let retry_config = RetryConfig::default().retry_status_codes(vec![503]);
+        let error = anyhow::Error::from(ProviderError::Upstream {
+            status: 503,
+            body: "unavailable".to_string(),
+        });
+
+        let actual = into_retry(error, &retry_config);
+
+        assert!(is_retryable(actual));
+        assert!(logs_contain("provider call failed, scheduling retry"));
+        assert!(logs_contain("Upstream error (status 503): unavailable"));
+    }
+
+    #[test]
+    fn test_backoff_for_falls_back_to_global_when_no_override_matches() {
+        let retry_config = RetryConfig::default().min_delay_ms(1000u64).max_delay(30u64);
+        let error = anyhow::Error::from(ProviderError::Upstream {
+            status: 500,
+            body: "internal error".to_string(),
+        });
+
+        let actual = backoff_for(&error, &retry_config);
+
+        assert_eq!(actual, (1000, Some(30)));
+    }
+
+    #[test]
+    fn test_backoff_for_a_429_and_a_503_with_distinct_overrides() {
+        let mut per_status_backoff = HashMap::new();
+        per_status_backoff.insert(429, BackoffOverride { min_delay_ms: 5000, max_delay: Some(60) });
+        per_status_backoff.insert(503, BackoffOverride { min_delay_ms: 500, max_delay: Some(10) });
+        let retry_config = RetryConfig::default()
+            .min_delay_ms(1000u64)
+            .max_delay(30u64)
+            .per_status_backoff(per_status_backoff);
+
+        let rate_limited =
+            ProviderError::Upstream { status: 429, body: "slow down".to_string() }.into();
+        let unavailable =
+            ProviderError::Upstream { status: 503, body: "unavailable".to_string() }.into();
+
+        let for_429 = backoff_for(&rate_limited, &retry_config);
+        let for_503 = backoff_for(&unavailable, &retry_config);
+
+        assert_eq!(for_429, (5000, Some(60)));
+        assert_eq!(for_503, (500, Some(10)));
+        assert_ne!(for_429, for_503);
+    }
+
+    #[test]
+    fn test_into_retry_marks_error_retryable_regardless_of_backoff_override() {
+        // The override picks the delay, but doesn't change whether the error
+        // is retried at all - that's still `retry_status_codes`/`is_retryable`.
+        let mut per_status_backoff = HashMap::new();
+        per_status_backoff.insert(429, BackoffOverride { min_delay_ms: 5000, max_delay: None });
+        let retry_config = RetryConfig::default().per_status_backoff(per_status_backoff);
+        let error =
+            anyhow::Error::from(ProviderError::Upstream { status: 429, body: "slow down".into() });
+
+        let actual = into_retry(error, &retry_config);
+
+        assert!(is_retryable(actual));
+    }
+
+    #[tokio::test]
+    async fn test_into_retry_classifies_connect_failure_as_connect_phase() {
+        // Nothing listens on this port, so the connection is refused almost
+        // immediately - `reqwest::Error::is_connect()` is true for this case
+        // just as it would be for a true black-hole address, without the
+        // test having to wait out a real connect timeout.
+        let client = reqwest::Client::new();
+        let raw_error = client
+            .get("http://127.0.0.1:1/")
+            .send()
+            .await
+            .expect_err("nothing should be listening on this port");
+
+        let retry_config = RetryConfig::default();
+        let actual = into_retry(anyhow::Error::from(raw_error), &retry_config);
+
+        assert!(matches!(
+            retryable_provider_error(&actual),
+            Some(ProviderError::Timeout { phase: TimeoutPhase::Connect })
+        ));
+        assert!(is_retryable(actual));
+    }
+
+    #[tokio::test]
+    async fn test_into_retry_classifies_read_stall_as_read_phase() {
+        use tokio::io::AsyncReadExt;
+        use tokio::net::TcpListener;
+
+        // A server that accepts the connection but never writes a response,
+        // so the client's read deadline (not the connect phase) is what
+        // fires.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            if let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                // Hold the connection open without responding.
+                tokio::time::sleep(Duration::from_secs(3600)).await;
+            }
+        });
+
+        let client = reqwest::Client::builder()
+            .read_timeout(Duration::from_millis(200))
+            .build()
+            .unwrap();
+        let raw_error = client
+            .get(format!("http://{addr}/"))
+            .send()
+            .await
+            .expect_err("the read deadline should fire before any response arrives");
+
+        let retry_config = RetryConfig::default();
+        let actual = into_retry(anyhow::Error::from(raw_error), &retry_config);
+
+        assert!(matches!(
+            retryable_provider_error(&actual),
+            Some(ProviderError::Timeout { phase: TimeoutPhase::Read })
+        ));
+        assert!(is_retryable(actual));
+    }
 }
@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use forge_app::domain::{ChatCompletionMessage, Context, Model, ModelId, ResultStream};
+
+use crate::error::ProviderError;
+use crate::Client;
+
+/// Routes a chat call to whichever [`Client`] actually serves the requested
+/// model, so an app that mostly talks to one provider can send a specific
+/// model to a different one without constructing and threading a second
+/// `Client` through by hand. Unlike [`crate::LoadBalancer`] - which spreads
+/// load across interchangeable members serving the *same* models - a
+/// `Router` assumes each model is served by exactly one client and picks by
+/// exact model-id match, falling back to a default client (if one was
+/// registered) for anything not explicitly routed.
+pub struct Router {
+    routes: HashMap<ModelId, Client>,
+    default: Option<Client>,
+}
+
+impl Router {
+    /// Builds a `Router` with no routes and no default; every call fails
+    /// with [`ProviderError::ModelNotFound`] until [`Router::route`] and/or
+    /// [`Router::with_default`] are used.
+    pub fn new() -> Self {
+        Self { routes: HashMap::new(), default: None }
+    }
+
+    /// Registers `client` as the one that serves `model`. Replaces any
+    /// existing route for the same model id.
+    pub fn route(mut self, model: ModelId, client: Client) -> Self {
+        self.routes.insert(model, client);
+        self
+    }
+
+    /// Sets the client used for any model with no explicit [`Router::route`]
+    /// entry, instead of failing with [`ProviderError::ModelNotFound`].
+    pub fn with_default(mut self, client: Client) -> Self {
+        self.default = Some(client);
+        self
+    }
+
+    /// The client that would serve `model`: an exact [`Router::route`] match,
+    /// falling back to the default client, or `None` if neither is set.
+    fn client_for(&self, model: &ModelId) -> Option<&Client> {
+        self.routes.get(model).or(self.default.as_ref())
+    }
+
+    /// Dispatches to whichever client serves `model`, failing with
+    /// [`ProviderError::ModelNotFound`] if no route or default client covers
+    /// it.
+    pub async fn chat(
+        &self,
+        model: &ModelId,
+        context: Context,
+    ) -> ResultStream<ChatCompletionMessage, anyhow::Error> {
+        let client = self
+            .client_for(model)
+            .ok_or_else(|| ProviderError::ModelNotFound(model.clone()))?;
+        client.chat(model, context).await
+    }
+
+    /// Lists models from every distinct client registered via
+    /// [`Router::route`]/[`Router::with_default`], deduplicated by
+    /// [`Model::id`] so a client used both as a route and as the default
+    /// doesn't contribute the same model twice.
+    pub async fn models(&self) -> anyhow::Result<Vec<Model>> {
+        let mut seen = std::collections::HashSet::new();
+        let mut models = Vec::new();
+        for client in self.routes.values().chain(self.default.iter()) {
+            for model in client.models().await? {
+                if seen.insert(model.id.clone()) {
+                    models.push(model);
+                }
+            }
+        }
+        Ok(models)
+    }
+}
+
+impl Default for Router {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use forge_app::domain::{Content, RetryConfig};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::mock_provider::MockProvider;
+
+    fn mock_client(reply: &str) -> Client {
+        let mock = MockProvider::builder().chat_response(vec![ChatCompletionMessage {
+            content: Some(Content::part(reply)),
+            ..Default::default()
+        }]);
+        Client::new_mock(mock, Arc::new(RetryConfig::default()))
+    }
+
+    async fn reply_of(stream: ResultStream<ChatCompletionMessage, anyhow::Error>) -> String {
+        let mut stream = stream.unwrap();
+        let message = futures::StreamExt::next(&mut stream).await.unwrap().unwrap();
+        message.content.unwrap().as_str().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_chat_dispatches_to_the_client_that_owns_the_model() {
+        let router = Router::new()
+            .route(ModelId::new("model-a"), mock_client("from-a"))
+            .route(ModelId::new("model-b"), mock_client("from-b"));
+
+        let a = router.chat(&ModelId::new("model-a"), Context::default()).await;
+        let b = router.chat(&ModelId::new("model-b"), Context::default()).await;
+
+        assert_eq!(reply_of(a).await, "from-a");
+        assert_eq!(reply_of(b).await, "from-b");
+    }
+
+    #[tokio::test]
+    async fn test_chat_falls_back_to_the_default_client_for_an_unrouted_model() {
+        let router = Router::new()
+            .route(ModelId::new("model-a"), mock_client("from-a"))
+            .with_default(mock_client("from-default"));
+
+        let result = router.chat(&ModelId::new("model-z"), Context::default()).await;
+
+        assert_eq!(reply_of(result).await, "from-default");
+    }
+
+    #[tokio::test]
+    async fn test_chat_fails_with_model_not_found_when_unrouted_and_no_default() {
+        let router = Router::new().route(ModelId::new("model-a"), mock_client("from-a"));
+
+        let result = router.chat(&ModelId::new("model-z"), Context::default()).await;
+
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err().downcast_ref::<ProviderError>(),
+            Some(ProviderError::ModelNotFound(model)) if *model == ModelId::new("model-z")
+        ));
+    }
+}
@@ -0,0 +1,70 @@
+use bytes::Bytes;
+use forge_app::domain::{BoxStream, ChatCompletionMessage};
+use futures::StreamExt;
+
+/// Adapts a chat response stream into Server-Sent-Events framing, for web
+/// apps proxying `Client::chat` output to a browser `EventSource`. Each
+/// [`ChatCompletionMessage`] is serialized to JSON and emitted as a plain
+/// `data:` event; a stream error becomes an `event: error` frame instead of
+/// ending the byte stream early, so a caller wired straight into an HTTP
+/// response body can still deliver it to the client; the stream always ends
+/// with a terminal `data: [DONE]` frame, mirroring OpenAI's own SSE
+/// convention.
+///
+/// This crate has no dependency on `axum` - or any other web framework - so
+/// the adapter produces raw SSE-formatted bytes rather than an
+/// `axum::response::sse::Event`. A caller on axum wraps the result in
+/// `axum::body::Body::from_stream` (or maps each chunk into `Event::default()
+/// .data(chunk)` if it wants typed events); this keeps the dependency out of
+/// every consumer that doesn't need it.
+pub fn into_sse_stream(
+    stream: BoxStream<ChatCompletionMessage, anyhow::Error>,
+) -> impl futures::Stream<Item = anyhow::Result<Bytes>> {
+    futures::stream::unfold(Some(stream), |state| async move {
+        let mut stream = state?;
+        match stream.next().await {
+            Some(Ok(message)) => {
+                let data = serde_json::to_string(&message).unwrap_or_default();
+                Some((Ok(Bytes::from(format!("data: {data}\n\n"))), Some(stream)))
+            }
+            Some(Err(err)) => Some((
+                Ok(Bytes::from(format!("event: error\ndata: {err}\n\n"))),
+                Some(stream),
+            )),
+            None => Some((Ok(Bytes::from_static(b"data: [DONE]\n\n")), None)),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_app::domain::Content;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn frame(bytes: &Bytes) -> String {
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_into_sse_stream_frames_messages_and_terminates_with_done() {
+        let messages = vec![
+            Ok(ChatCompletionMessage::assistant(Content::part("hello"))),
+            Err(anyhow::anyhow!("upstream exploded")),
+        ];
+        let stream: BoxStream<ChatCompletionMessage, anyhow::Error> =
+            Box::pin(futures::stream::iter(messages));
+
+        let frames: Vec<Bytes> = into_sse_stream(stream).map(|r| r.unwrap()).collect().await;
+
+        assert_eq!(frames.len(), 3);
+        assert!(frame(&frames[0]).starts_with("data: "));
+        assert!(frame(&frames[0]).contains("hello"));
+        assert_eq!(
+            frame(&frames[1]),
+            "event: error\ndata: upstream exploded\n\n"
+        );
+        assert_eq!(frame(&frames[2]), "data: [DONE]\n\n");
+    }
+}
@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use futures::Stream;
+use tokio_stream::StreamExt;
+
+use crate::error::ProviderError;
+
+/// Wraps `stream` with a two-phase read deadline: `first_token` bounds the
+/// wait for the first item, `inter_token` (reset on every subsequent item)
+/// bounds the wait for each item after that. A provider that accepts the
+/// connection but never starts responding fails fast against `first_token`
+/// instead of waiting out `inter_token`, while a slow-but-steady response is
+/// never killed since the deadline resets on every chunk.
+pub fn with_token_timeouts<S>(
+    stream: S,
+    first_token: Duration,
+    inter_token: Duration,
+) -> impl Stream<Item = Result<S::Item, ProviderError>>
+where
+    S: Stream + Unpin,
+{
+    futures::stream::unfold((stream, true), move |(mut stream, is_first)| async move {
+        let deadline = if is_first { first_token } else { inter_token };
+        match tokio::time::timeout(deadline, stream.next()).await {
+            Ok(Some(item)) => Some((Ok(item), (stream, false))),
+            Ok(None) => None,
+            Err(_) => {
+                let error = if is_first {
+                    ProviderError::FirstTokenTimeout
+                } else {
+                    ProviderError::InterTokenTimeout
+                };
+                Some((Err(error), (stream, false)))
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_first_token_timeout_when_stream_never_sends() {
+        let stream = Box::pin(futures::stream::pending::<i32>());
+        let mut timed =
+            with_token_timeouts(stream, Duration::from_secs(1), Duration::from_secs(60));
+
+        let actual = timed.next().await.unwrap();
+        assert!(matches!(actual, Err(ProviderError::FirstTokenTimeout)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_inter_token_timeout_when_stream_sends_then_stalls() {
+        let stream =
+            Box::pin(futures::stream::once(async { 1 }).chain(futures::stream::pending()));
+        let mut timed =
+            with_token_timeouts(stream, Duration::from_secs(60), Duration::from_secs(1));
+
+        let first = timed.next().await.unwrap();
+        assert_eq!(first.unwrap(), 1);
+
+        let actual = timed.next().await.unwrap();
+        assert!(matches!(actual, Err(ProviderError::InterTokenTimeout)));
+    }
+}
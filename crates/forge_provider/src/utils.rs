@@ -1,5 +1,9 @@
+use anyhow::Context;
+use bytes::Bytes;
+use futures::Stream;
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
-use reqwest::StatusCode;
+use reqwest::{StatusCode, Url};
+use tokio_stream::StreamExt;
 
 /// Helper function to format HTTP request/response context for logging and
 /// error reporting
@@ -15,9 +19,71 @@ pub(crate) fn format_http_context<U: AsRef<str>>(
     }
 }
 
+/// Extracts the upstream request ID from a response, checking OpenAI's
+/// `x-request-id` header first, then Anthropic's `request-id`, so a failing
+/// call can surface the ID that upstream support needs to look up a ticket.
+/// Returns `None` if neither header is present or the value isn't valid
+/// UTF-8.
+pub(crate) fn extract_request_id(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get("x-request-id")
+        .or_else(|| headers.get("request-id"))
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+}
+
+/// Adds a context layer naming `request_id` to a failing `result`, if one
+/// was found on the response. No-op when `request_id` is `None`, so callers
+/// can chain it unconditionally alongside [`format_http_context`].
+pub(crate) fn with_request_id_context<T>(
+    result: anyhow::Result<T>,
+    request_id: Option<&str>,
+) -> anyhow::Result<T> {
+    match request_id {
+        Some(id) => result.with_context(|| format!("request-id: {id}")),
+        None => result,
+    }
+}
+
+/// Serializes `value` to JSON, merging in `extra_body`'s fields (see
+/// [`forge_app::domain::ChatOptions::extra_body`]) if any are set, and checks
+/// the result against `limit` before returning it, so a caller can reject an
+/// oversized request (e.g. an accidentally huge [`forge_app::domain::Context`])
+/// with the actual serialized byte count rather than sending it and hoping
+/// upstream handles it gracefully. A `None` limit always succeeds. A key in
+/// `extra_body` that collides with one `value` already serializes is left
+/// alone - crate-managed fields always win over an extra field of the same
+/// name, since the crate's own request-building logic already validated or
+/// derived that value.
+pub(crate) fn serialize_with_size_guard<T: serde::Serialize>(
+    value: &T,
+    limit: Option<u64>,
+    extra_body: Option<&serde_json::Map<String, serde_json::Value>>,
+) -> anyhow::Result<Vec<u8>> {
+    let bytes = match extra_body {
+        Some(extra) if !extra.is_empty() => {
+            let mut json = serde_json::to_value(value)?;
+            if let serde_json::Value::Object(map) = &mut json {
+                for (key, extra_value) in extra {
+                    map.entry(key.clone()).or_insert_with(|| extra_value.clone());
+                }
+            }
+            serde_json::to_vec(&json)?
+        }
+        _ => serde_json::to_vec(value)?,
+    };
+    if let Some(limit) = limit {
+        let size = bytes.len() as u64;
+        if size > limit {
+            return Err(crate::error::ProviderError::RequestTooLarge { size, limit }.into());
+        }
+    }
+    Ok(bytes)
+}
+
 /// Sanitizes headers for logging by redacting sensitive values
 pub fn sanitize_headers(headers: &HeaderMap) -> HeaderMap {
-    let sensitive_headers = [AUTHORIZATION.as_str()];
+    let sensitive_headers = [AUTHORIZATION.as_str(), "x-api-key", "api-key"];
     headers
         .iter()
         .map(|(name, value)| {
@@ -32,12 +98,190 @@ pub fn sanitize_headers(headers: &HeaderMap) -> HeaderMap {
         .collect()
 }
 
+/// Computes the Levenshtein edit distance between `a` and `b`, i.e. the
+/// minimum number of single-character insertions, deletions, or
+/// substitutions needed to turn one into the other. Used to rank "did you
+/// mean" suggestions for a model id that doesn't match any known model (see
+/// [`crate::client::did_you_mean`]).
+pub(crate) fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut prev_diag = row[0];
+        row[0] = i;
+        for j in 1..=b.len() {
+            let cur = row[j];
+            row[j] = if a[i - 1] == b[j - 1] {
+                prev_diag
+            } else {
+                1 + prev_diag.min(row[j]).min(row[j - 1])
+            };
+            prev_diag = cur;
+        }
+    }
+    row[b.len()]
+}
+
+/// Converts a header map into a JSON object of string values, for embedding
+/// in dry-run request previews (e.g. [`crate::client::Client::build_chat_request`]).
+/// Header values that aren't valid UTF-8 are rendered as `"<binary>"`.
+pub fn headers_to_json(headers: &HeaderMap) -> serde_json::Value {
+    let map: serde_json::Map<String, serde_json::Value> = headers
+        .iter()
+        .map(|(name, value)| {
+            let value = value.to_str().unwrap_or("<binary>").to_string();
+            (name.as_str().to_string(), serde_json::Value::String(value))
+        })
+        .collect();
+    serde_json::Value::Object(map)
+}
+
+/// Appends `path` to `base`, preserving every path segment already in
+/// `base` - including a custom prefix a gateway mounts the API under (e.g.
+/// `/api/openai/v1`). `Url::join` alone would silently replace `base`'s last
+/// segment with `path` unless `base` already ends in `/`, so a user-supplied
+/// base URL without a trailing slash would lose its final segment (e.g. the
+/// `v1` in `/api/openai/v1`) the first time a path was appended to it. This
+/// normalizes `base` to end in `/` before joining, so callers don't have to
+/// rely on every construction site remembering to do it themselves.
+pub(crate) fn join_base_url(base: &Url, path: &str) -> anyhow::Result<Url> {
+    if path.contains("://") || path.contains("..") {
+        anyhow::bail!("Invalid path: Contains forbidden patterns");
+    }
+    let path = path.trim_start_matches('/');
+
+    let mut base = base.clone();
+    if !base.path().ends_with('/') {
+        let path_with_slash = format!("{}/", base.path());
+        base.set_path(&path_with_slash);
+    }
+
+    base.join(path)
+        .with_context(|| format!("Failed to append {path} to base URL: {base}"))
+}
+
+/// Normalizes a user-supplied stop-sequence list against a provider's own
+/// limit on how many it accepts (e.g. OpenAI's `stop` field allows at most
+/// 4). An absent or empty list normalizes to `None`, so "no stops" is never
+/// sent as an empty array. Exceeding `limit` fails with
+/// [`crate::error::ProviderError::TooManyStopSequences`] rather than
+/// silently truncating - dropping a caller-specified stop sequence changes
+/// generation behavior in a way that should never happen invisibly.
+pub(crate) fn normalize_stop_sequences(
+    stop: Option<&[String]>,
+    limit: usize,
+) -> anyhow::Result<Option<Vec<String>>> {
+    let Some(stop) = stop else { return Ok(None) };
+    if stop.is_empty() {
+        return Ok(None);
+    }
+    if stop.len() > limit {
+        return Err(
+            crate::error::ProviderError::TooManyStopSequences { count: stop.len(), limit }.into(),
+        );
+    }
+    Ok(Some(stop.to_vec()))
+}
+
+/// Splits a byte stream on newlines, for providers (Ollama, Cohere) whose
+/// streaming endpoint sends one JSON object per line (NDJSON) rather than
+/// server-sent events. Bytes are buffered raw and only decoded once a
+/// complete `\n`-terminated line has been assembled, so a multibyte UTF-8
+/// character split across two chunks by the underlying transport is never
+/// decoded mid-codepoint - `\n` is `0x0A`, and UTF-8 continuation bytes are
+/// always `>= 0x80`, so the delimiter can't occur inside one.
+pub(crate) fn ndjson_lines<S>(byte_stream: S) -> impl Stream<Item = anyhow::Result<String>>
+where
+    S: Stream<Item = reqwest::Result<Bytes>> + Unpin,
+{
+    futures::stream::unfold(
+        (byte_stream, Vec::<u8>::new(), false),
+        |(mut byte_stream, mut buf, mut upstream_done)| async move {
+            loop {
+                if let Some(pos) = buf.iter().position(|&b| b == b'\n') {
+                    let line: Vec<u8> = buf.drain(..=pos).collect();
+                    let line = String::from_utf8_lossy(&line[..line.len() - 1]).into_owned();
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    return Some((Ok(line), (byte_stream, buf, upstream_done)));
+                }
+
+                if upstream_done {
+                    if buf.is_empty() {
+                        return None;
+                    }
+                    let line = String::from_utf8_lossy(&buf).into_owned();
+                    buf.clear();
+                    return Some((Ok(line), (byte_stream, buf, upstream_done)));
+                }
+
+                match byte_stream.next().await {
+                    Some(Ok(bytes)) => buf.extend_from_slice(&bytes),
+                    Some(Err(err)) => {
+                        return Some((Err(err.into()), (byte_stream, buf, true)));
+                    }
+                    None => upstream_done = true,
+                }
+            }
+        },
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use reqwest::header::HeaderValue;
 
     use super::*;
 
+    #[test]
+    fn test_levenshtein_distance() {
+        assert_eq!(levenshtein_distance("gpt4o", "gpt-4o"), 1);
+        assert_eq!(levenshtein_distance("same", "same"), 0);
+        assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+    }
+
+    #[derive(serde::Serialize)]
+    struct Fixture {
+        model: &'static str,
+    }
+
+    #[test]
+    fn test_serialize_with_size_guard_merges_extra_body_fields() {
+        let fixture = Fixture { model: "gpt-4" };
+        let mut extra = serde_json::Map::new();
+        extra.insert("service_tier".to_string(), serde_json::json!("flex"));
+
+        let bytes = serialize_with_size_guard(&fixture, None, Some(&extra)).unwrap();
+        let actual: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+        assert_eq!(actual["model"], "gpt-4");
+        assert_eq!(actual["service_tier"], "flex");
+    }
+
+    #[test]
+    fn test_serialize_with_size_guard_extra_body_never_overrides_a_crate_managed_field() {
+        let fixture = Fixture { model: "gpt-4" };
+        let mut extra = serde_json::Map::new();
+        extra.insert("model".to_string(), serde_json::json!("attacker-controlled"));
+
+        let bytes = serialize_with_size_guard(&fixture, None, Some(&extra)).unwrap();
+        let actual: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+        assert_eq!(actual["model"], "gpt-4");
+    }
+
+    #[test]
+    fn test_serialize_with_size_guard_without_extra_body_is_unaffected() {
+        let fixture = Fixture { model: "gpt-4" };
+        let bytes = serialize_with_size_guard(&fixture, None, None).unwrap();
+        let actual: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+        assert_eq!(actual, serde_json::json!({ "model": "gpt-4" }));
+    }
+
     #[test]
     fn test_sanitize_headers_for_logging() {
         let mut headers = HeaderMap::new();
@@ -64,4 +308,91 @@ mod tests {
             Some(&HeaderValue::from_static("application/json"))
         );
     }
+
+    #[test]
+    fn test_join_base_url_respects_a_trailing_slash() {
+        let base = Url::parse("https://api.openai.com/v1/").unwrap();
+        let actual = join_base_url(&base, "chat/completions").unwrap();
+        assert_eq!(actual.as_str(), "https://api.openai.com/v1/chat/completions");
+    }
+
+    #[test]
+    fn test_join_base_url_adds_a_missing_trailing_slash_instead_of_dropping_the_last_segment() {
+        let base = Url::parse("https://api.openai.com/v1").unwrap();
+        let actual = join_base_url(&base, "chat/completions").unwrap();
+        assert_eq!(actual.as_str(), "https://api.openai.com/v1/chat/completions");
+    }
+
+    #[test]
+    fn test_join_base_url_preserves_a_custom_gateway_path_prefix() {
+        let base = Url::parse("https://gateway.example.com/api/openai/v1").unwrap();
+        let actual = join_base_url(&base, "chat/completions").unwrap();
+        assert_eq!(
+            actual.as_str(),
+            "https://gateway.example.com/api/openai/v1/chat/completions"
+        );
+    }
+
+    #[test]
+    fn test_join_base_url_preserves_a_custom_gateway_path_prefix_with_trailing_slash() {
+        let base = Url::parse("https://gateway.example.com/api/openai/v1/").unwrap();
+        let actual = join_base_url(&base, "chat/completions").unwrap();
+        assert_eq!(
+            actual.as_str(),
+            "https://gateway.example.com/api/openai/v1/chat/completions"
+        );
+    }
+
+    #[test]
+    fn test_join_base_url_trims_a_leading_slash_from_the_path() {
+        let base = Url::parse("https://api.openai.com/v1/").unwrap();
+        let actual = join_base_url(&base, "/chat/completions").unwrap();
+        assert_eq!(actual.as_str(), "https://api.openai.com/v1/chat/completions");
+    }
+
+    #[test]
+    fn test_join_base_url_rejects_a_path_with_forbidden_patterns() {
+        let base = Url::parse("https://api.openai.com/v1/").unwrap();
+        assert!(join_base_url(&base, "../secrets").is_err());
+        assert!(join_base_url(&base, "https://evil.example.com").is_err());
+    }
+
+    #[test]
+    fn test_normalize_stop_sequences_treats_none_and_empty_as_no_stops() {
+        assert_eq!(normalize_stop_sequences(None, 4).unwrap(), None);
+        assert_eq!(normalize_stop_sequences(Some(&[]), 4).unwrap(), None);
+    }
+
+    #[test]
+    fn test_normalize_stop_sequences_passes_through_within_the_limit() {
+        let stop = vec!["STOP".to_string(), "END".to_string()];
+        assert_eq!(normalize_stop_sequences(Some(&stop), 4).unwrap(), Some(stop));
+    }
+
+    #[test]
+    fn test_normalize_stop_sequences_rejects_exceeding_the_limit() {
+        let stop = vec!["a".to_string(), "b".to_string(), "c".to_string(), "d".to_string(), "e".to_string()];
+        let err = normalize_stop_sequences(Some(&stop), 4).unwrap_err();
+        assert!(err.to_string().contains("Too many stop sequences"));
+    }
+
+    #[tokio::test]
+    async fn test_ndjson_lines_reconstructs_a_multibyte_character_split_across_chunk_boundaries() {
+        // "😀" is 4 UTF-8 bytes (F0 9F 98 80); split the chunk boundary in the
+        // middle of it, so neither half is valid UTF-8 on its own.
+        let line = "hello 😀 文 world";
+        let mut bytes = line.as_bytes().to_vec();
+        bytes.push(b'\n');
+        let split_at = bytes.iter().position(|&b| b == 0x9F).unwrap() + 1;
+        let (first, second) = bytes.split_at(split_at);
+
+        let chunks: Vec<reqwest::Result<Bytes>> =
+            vec![Ok(Bytes::copy_from_slice(first)), Ok(Bytes::copy_from_slice(second))];
+        let stream = futures::stream::iter(chunks);
+
+        let stream = futures::StreamExt::map(ndjson_lines(stream), |result| result.unwrap());
+        let lines: Vec<String> = futures::StreamExt::collect(stream).await;
+
+        assert_eq!(lines, vec![line.to_string()]);
+    }
 }
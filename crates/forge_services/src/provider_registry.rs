@@ -60,14 +60,17 @@ fn resolve_env_provider<F: EnvironmentInfra>(
     url: Option<ProviderUrl>,
     env: &F,
 ) -> Option<Provider> {
-    let keys: [ProviderSearch; 7] = [
+    let keys: [ProviderSearch; 10] = [
         ("FORGE_KEY", Box::new(Provider::forge)),
         ("OPENROUTER_API_KEY", Box::new(Provider::open_router)),
         ("REQUESTY_API_KEY", Box::new(Provider::requesty)),
         ("XAI_API_KEY", Box::new(Provider::xai)),
+        ("MISTRAL_API_KEY", Box::new(Provider::mistral)),
         ("OPENAI_API_KEY", Box::new(Provider::openai)),
         ("ANTHROPIC_API_KEY", Box::new(Provider::anthropic)),
         ("GITHUB_COPILOT_TOKEN", Box::new(Provider::copilot)),
+        ("GEMINI_API_KEY", Box::new(Provider::gemini)),
+        ("COHERE_API_KEY", Box::new(Provider::cohere)),
     ];
 
     keys.into_iter().find_map(|(key, fun)| {
@@ -113,7 +116,17 @@ mod tests {
         let registry = ForgeProviderRegistry::new(infra);
         let provider = registry.get_provider(AppConfig::default());
         assert!(
-            matches!(provider, Some(Provider::OpenAI { url, key: Some(ref k), extra_headers: Some(ref headers) }) if url.as_str().starts_with("https://api.githubcopilot.com/") && k == "copilot_test_token" && headers.contains_key("Copilot-Integration-Id"))
+            matches!(
+                provider,
+                Some(Provider::OpenAI {
+                    url,
+                    key: Some(ref k),
+                    extra_headers: Some(ref headers),
+                    ..
+                }) if url.as_str().starts_with("https://api.githubcopilot.com/")
+                    && k == "copilot_test_token"
+                    && headers.contains_key("Copilot-Integration-Id")
+            )
         );
     }
 }